@@ -1,10 +1,19 @@
-use clap::{Parser, Subcommand};
+use chrono::Utc;
+use clap::{ArgGroup, Args, Parser, Subcommand, ValueEnum};
+use std::io::Write;
+use std::path::{Path, PathBuf};
 use std::process;
+use std::time::Duration;
 use tokio::runtime::Runtime;
 
-use crate::config::JobManager;
-use crate::daemon::Daemon;
+use crate::config::{Config, JobManager};
+use crate::control::{self, Request, Response};
+use crate::daemon::{Daemon, STOP_MARGIN};
 use crate::errors::{CronrError, Result};
+use crate::history::RunOutcome;
+use crate::job::{CatchUpPolicy, ExecMode, Job, OverlapPolicy, RetryPolicy, ShutdownPolicy};
+use crate::logger::LogFollower;
+use crate::schedule::{JobSchedule, JobTimeZone};
 
 /// Command-line arguments for the cron manager
 #[derive(Parser, Debug)]
@@ -27,17 +36,178 @@ pub enum Commands {
         /// The cron expression (e.g., "0 * * * *" for every hour)
         #[clap(name = "schedule")]
         cron_expression: String,
+
+        /// The time zone to evaluate the schedule in (defaults to the system time zone)
+        #[clap(long, value_name = "ZONE")]
+        timezone: Option<String>,
+
+        /// Tag the job (repeatable)
+        #[clap(long = "tag", value_name = "TAG", value_parser = parse_tag)]
+        tags: Vec<String>,
+
+        /// Set an environment variable for the job (KEY=VALUE, repeatable)
+        #[clap(long = "env", value_name = "KEY=VALUE", value_parser = parse_env_var)]
+        env: Vec<(String, String)>,
+
+        #[clap(flatten)]
+        metadata: JobMetadata,
+
+        #[clap(flatten)]
+        execution: ExecutionOptions,
     },
 
     /// List all cron jobs
     #[clap(name = "ls")]
-    List,
+    List {
+        /// Only list jobs with this tag
+        #[clap(long)]
+        tag: Option<String>,
+    },
 
-    /// Stop a cron job
+    /// Show the details of a cron job
+    #[clap(name = "show")]
+    Show {
+        /// The ID or name of the job to show
+        job: String,
+
+        /// Number of upcoming fire times to list
+        #[clap(short = 'n', long, default_value_t = 5)]
+        count: usize,
+    },
+
+    /// Stop a cron job, or all jobs with a tag
     #[clap(name = "stop")]
     Stop {
-        /// The ID of the job to stop
-        id: usize,
+        #[clap(flatten)]
+        selector: JobSelector,
+    },
+
+    /// Pause a cron job, or all jobs with a tag, without deleting them
+    #[clap(name = "pause")]
+    Pause {
+        #[clap(flatten)]
+        selector: JobSelector,
+    },
+
+    /// Resume a paused cron job, or all paused jobs with a tag
+    #[clap(name = "resume")]
+    Resume {
+        #[clap(flatten)]
+        selector: JobSelector,
+    },
+
+    /// Run a cron job immediately, outside its schedule
+    #[clap(name = "run")]
+    Run {
+        /// The ID or name of the job to run
+        job: String,
+
+        /// Stream the job's output to the terminal and exit with its exit code
+        #[clap(long)]
+        foreground: bool,
+    },
+
+    /// Terminate the runs of a cron job that are in progress in the daemon
+    #[clap(name = "kill")]
+    Kill {
+        /// The ID or name of the job
+        job: String,
+    },
+
+    /// Show the output logs of a cron job
+    #[clap(name = "logs")]
+    Logs {
+        /// The ID or name of the job
+        job: String,
+
+        /// Number of lines to show from the end of each log
+        #[clap(short = 'n', long, default_value_t = 10)]
+        lines: usize,
+
+        /// Which output stream to show
+        #[clap(long, value_enum, default_value_t = LogStream::Both)]
+        stream: LogStream,
+
+        /// Keep printing new output as it is written, including after log rotation
+        #[clap(short, long)]
+        follow: bool,
+    },
+
+    /// Show the run history of a cron job
+    #[clap(name = "history")]
+    History {
+        /// The ID or name of the job
+        job: String,
+
+        /// Number of most recent runs to show
+        #[clap(short = 'n', long, default_value_t = 10)]
+        limit: usize,
+
+        /// Only show runs that did not succeed
+        #[clap(long)]
+        failed: bool,
+    },
+
+    /// Edit an existing cron job in place
+    #[clap(name = "edit")]
+    #[clap(group(
+        ArgGroup::new("changes")
+            .required(true)
+            .multiple(true)
+            .args([
+                "name",
+                "description",
+                "owner",
+                "timeout",
+                "kill_grace",
+                "max_attempts",
+                "retry_delay",
+                "retry_backoff",
+                "retry_max_delay",
+                "overlap",
+                "catch_up",
+                "catch_up_limit",
+                "max_lateness",
+                "concurrency_group",
+                "priority",
+                "cwd",
+                "exec_mode",
+                "shell",
+                "on_shutdown",
+            ])
+    ))]
+    Edit {
+        /// The ID or name of the job to edit
+        job: String,
+
+        #[clap(flatten)]
+        changes: JobChanges,
+    },
+
+    /// View or change the environment variables of a cron job
+    #[clap(name = "env")]
+    Env {
+        /// The ID or name of the job
+        job: String,
+
+        #[clap(subcommand)]
+        action: EnvAction,
+    },
+
+    /// Preview the next fire times of a cron expression without creating a job
+    #[clap(name = "next")]
+    Next {
+        /// The cron expression to check
+        #[clap(name = "schedule")]
+        cron_expression: String,
+
+        /// Number of fire times to list
+        #[clap(short = 'n', long, default_value_t = 5)]
+        count: usize,
+
+        /// The time zone to evaluate the schedule in (defaults to the system time zone)
+        #[clap(long, value_name = "ZONE")]
+        timezone: Option<String>,
     },
 
     /// Show version information
@@ -61,6 +231,348 @@ pub enum Commands {
     DaemonInternal,
 }
 
+/// Actions of the `env` command
+#[derive(Subcommand, Debug)]
+pub enum EnvAction {
+    /// List the job's environment variables and their values
+    #[clap(name = "ls")]
+    List,
+
+    /// Set one or more environment variables
+    #[clap(name = "set")]
+    Set {
+        /// The variables to set
+        #[clap(value_name = "KEY=VALUE", required = true, value_parser = parse_env_var)]
+        vars: Vec<(String, String)>,
+    },
+
+    /// Remove one or more environment variables
+    #[clap(name = "unset")]
+    Unset {
+        /// The names of the variables to remove
+        #[clap(value_name = "KEY", required = true)]
+        keys: Vec<String>,
+    },
+}
+
+/// The jobs a bulk command applies to: a single job, or all jobs with a tag
+#[derive(Args, Debug)]
+#[group(required = true, multiple = false)]
+pub struct JobSelector {
+    /// The ID or name of the job
+    job: Option<String>,
+
+    /// Apply to all jobs with this tag instead
+    #[clap(long)]
+    tag: Option<String>,
+}
+
+/// Changes to a job applied by `edit`
+#[derive(Args, Debug)]
+pub struct JobChanges {
+    /// The new command to execute
+    #[clap(long, group = "changes")]
+    command: Option<String>,
+
+    /// The new cron expression
+    #[clap(long = "schedule", group = "changes")]
+    cron_expression: Option<String>,
+
+    /// The new time zone to evaluate the schedule in
+    #[clap(long, value_name = "ZONE", group = "changes")]
+    timezone: Option<String>,
+
+    /// Set an environment variable for the job (KEY=VALUE, repeatable)
+    #[clap(long = "env", value_name = "KEY=VALUE", value_parser = parse_env_var, group = "changes")]
+    env: Vec<(String, String)>,
+
+    /// Remove an environment variable from the job (repeatable)
+    #[clap(long = "unset-env", value_name = "KEY", group = "changes")]
+    unset_env: Vec<String>,
+
+    /// Add a tag to the job (repeatable)
+    #[clap(long = "tag", value_name = "TAG", value_parser = parse_tag, group = "changes")]
+    tags: Vec<String>,
+
+    /// Remove a tag from the job (repeatable)
+    #[clap(long = "untag", value_name = "TAG", group = "changes")]
+    untags: Vec<String>,
+
+    #[clap(flatten)]
+    metadata: JobMetadata,
+
+    #[clap(flatten)]
+    execution: ExecutionOptions,
+}
+
+impl JobChanges {
+    /// Apply the changes to a job, validating each one
+    fn apply(self, job: &mut Job) -> Result<()> {
+        if let Some(command) = self.command {
+            job.command = command;
+        }
+        if let Some(timezone) = self.timezone {
+            job.set_time_zone(timezone)?;
+        }
+        if let Some(cron_expression) = self.cron_expression {
+            job.set_schedule(cron_expression)?;
+        }
+        for key in self.unset_env {
+            job.env.remove(&key);
+        }
+        for (key, value) in self.env {
+            job.env.insert(key, value);
+        }
+        for tag in self.untags {
+            job.remove_tag(&tag);
+        }
+        for tag in self.tags {
+            job.add_tag(tag);
+        }
+        self.execution.apply(job);
+        self.metadata.apply(job)?;
+
+        // Check the command can still be run in the job's execution mode
+        job.program().map(|_| ())
+    }
+}
+
+impl JobSelector {
+    /// Get the IDs of the selected jobs
+    async fn resolve(&self, job_manager: &JobManager) -> Result<Vec<usize>> {
+        match (&self.job, &self.tag) {
+            (Some(job), _) => Ok(vec![job_manager.resolve_job_id(job).await?]),
+            (None, Some(tag)) => Ok(job_manager.job_ids_with_tag(tag).await),
+            (None, None) => Ok(Vec::new()),
+        }
+    }
+}
+
+/// Descriptive fields of a job, set by `create` and changed by `edit`
+#[derive(Args, Debug)]
+pub struct JobMetadata {
+    /// A unique name to refer to the job by instead of its ID (empty to clear)
+    #[clap(long)]
+    name: Option<String>,
+
+    /// What the job is for (empty to clear)
+    #[clap(long)]
+    description: Option<String>,
+
+    /// Who is responsible for the job (empty to clear)
+    #[clap(long)]
+    owner: Option<String>,
+}
+
+impl JobMetadata {
+    /// Apply the given fields to a job, clearing those given as empty
+    fn apply(self, job: &mut Job) -> Result<()> {
+        let non_empty = |value: String| (!value.is_empty()).then_some(value);
+        if let Some(name) = self.name {
+            job.set_name(non_empty(name))?;
+        }
+        if let Some(description) = self.description {
+            job.description = non_empty(description);
+        }
+        if let Some(owner) = self.owner {
+            job.owner = non_empty(owner);
+        }
+        Ok(())
+    }
+}
+
+/// How a job's runs are executed, set by `create` and changed by `edit`
+#[derive(Args, Debug)]
+pub struct ExecutionOptions {
+    /// Terminate runs that take longer than this, e.g. 90s, 30m or 2h (0 for no limit)
+    #[clap(long, value_name = "DURATION", value_parser = parse_duration)]
+    timeout: Option<u64>,
+
+    /// Time to wait after SIGTERM before sending SIGKILL to a timed out run [default: 10s]
+    #[clap(long, value_name = "DURATION", value_parser = parse_duration)]
+    kill_grace: Option<u64>,
+
+    /// Maximum attempts per fire time when runs fail, including the first (1 to disable retries)
+    #[clap(long, value_name = "N", value_parser = clap::value_parser!(u32).range(1..))]
+    max_attempts: Option<u32>,
+
+    /// Delay before the first retry [default: 30s]
+    #[clap(long, value_name = "DURATION", value_parser = parse_duration)]
+    retry_delay: Option<u64>,
+
+    /// Factor the retry delay is multiplied by after each retry [default: 2]
+    #[clap(long, value_name = "FACTOR", value_parser = parse_backoff)]
+    retry_backoff: Option<f64>,
+
+    /// Upper bound for the retry delay [default: 1h]
+    #[clap(long, value_name = "DURATION", value_parser = parse_duration)]
+    retry_max_delay: Option<u64>,
+
+    /// What to do when the job is due while its previous run is still going [default: skip]
+    #[clap(long, value_enum)]
+    overlap: Option<OverlapArg>,
+
+    /// What to do with fire times missed while the daemon was down [default: once]
+    #[clap(long, value_enum)]
+    catch_up: Option<CatchUpArg>,
+
+    /// Maximum number of missed fire times run by `--catch-up all` [default: 10]
+    #[clap(long, value_name = "N", value_parser = clap::value_parser!(u32).range(1..))]
+    catch_up_limit: Option<u32>,
+
+    /// Drop missed fire times that are later than this, e.g. 30m or 1d (0 for no limit)
+    #[clap(long, value_name = "DURATION", value_parser = parse_duration)]
+    max_lateness: Option<u64>,
+
+    /// Concurrency group whose limit the job's runs count against (empty to remove)
+    #[clap(long = "group", value_name = "GROUP")]
+    concurrency_group: Option<String>,
+
+    /// Priority when waiting for a free slot, higher runs first [default: 0]
+    #[clap(long, allow_negative_numbers = true)]
+    priority: Option<i32>,
+
+    /// Directory the command runs in [default: the current directory]
+    #[clap(long, value_name = "DIR", value_parser = parse_working_dir)]
+    cwd: Option<PathBuf>,
+
+    /// How to run the command [default: login-shell]
+    #[clap(long = "exec", value_enum, value_name = "MODE")]
+    exec_mode: Option<ExecModeArg>,
+
+    /// Shell to run the command in instead of $SHELL (empty to use $SHELL again)
+    #[clap(long, value_name = "PATH")]
+    shell: Option<String>,
+
+    /// What happens to a run in progress when the daemon shuts down [default: stop]
+    #[clap(long, value_enum)]
+    on_shutdown: Option<ShutdownArg>,
+}
+
+impl ExecutionOptions {
+    /// Apply the given options to a job
+    fn apply(self, job: &mut Job) {
+        if let Some(timeout) = self.timeout {
+            job.timeout_secs = (timeout > 0).then_some(timeout);
+        }
+        if let Some(kill_grace) = self.kill_grace {
+            job.kill_grace_secs = Some(kill_grace);
+        }
+
+        // Any retry option sets up a policy, starting from the defaults
+        let retry_given = self.max_attempts.is_some()
+            || self.retry_delay.is_some()
+            || self.retry_backoff.is_some()
+            || self.retry_max_delay.is_some();
+        if retry_given {
+            let retry = job.retry.get_or_insert_with(RetryPolicy::default);
+            if let Some(max_attempts) = self.max_attempts {
+                retry.max_attempts = max_attempts;
+            }
+            if let Some(delay) = self.retry_delay {
+                retry.initial_delay_secs = delay;
+            }
+            if let Some(multiplier) = self.retry_backoff {
+                retry.multiplier = multiplier;
+            }
+            if let Some(max_delay) = self.retry_max_delay {
+                retry.max_delay_secs = max_delay;
+            }
+            if retry.max_attempts <= 1 {
+                job.retry = None;
+            }
+        }
+
+        if let Some(overlap) = self.overlap {
+            job.overlap = overlap.into();
+        }
+
+        if let Some(catch_up) = self.catch_up {
+            job.catch_up = catch_up.into();
+        }
+        if let Some(limit) = self.catch_up_limit {
+            job.catch_up_limit = Some(limit);
+        }
+        if let Some(lateness) = self.max_lateness {
+            job.max_lateness_secs = (lateness > 0).then_some(lateness);
+        }
+
+        if let Some(group) = self.concurrency_group {
+            job.concurrency_group = (!group.is_empty()).then_some(group);
+        }
+        if let Some(priority) = self.priority {
+            job.priority = priority;
+        }
+
+        if let Some(dir) = self.cwd {
+            job.working_dir = Some(dir);
+        }
+
+        if let Some(exec_mode) = self.exec_mode {
+            job.exec_mode = exec_mode.into();
+        }
+        if let Some(shell) = self.shell {
+            job.shell = (!shell.is_empty()).then_some(shell);
+        }
+
+        if let Some(on_shutdown) = self.on_shutdown {
+            job.on_shutdown = on_shutdown.into();
+        }
+    }
+}
+
+/// Output streams of a job that can be shown by `logs`
+#[derive(ValueEnum, Clone, Copy, Debug, PartialEq)]
+pub enum LogStream {
+    /// Standard output only
+    Stdout,
+    /// Standard error only
+    Stderr,
+    /// Both standard output and standard error
+    Both,
+}
+
+/// Declare the values of an option that picks a variant of a job enum, as a
+/// `ValueEnum` with the same variants. The variants are described in the job
+/// module and the README rather than in `--help`.
+macro_rules! job_enum_arg {
+    ($(#[$meta:meta])* $arg:ident => $target:ident { $($variant:ident),+ $(,)? }) => {
+        $(#[$meta])*
+        #[derive(ValueEnum, Clone, Copy, Debug, PartialEq)]
+        enum $arg {
+            $($variant),+
+        }
+
+        impl From<$arg> for $target {
+            fn from(arg: $arg) -> Self {
+                match arg {
+                    $($arg::$variant => $target::$variant),+
+                }
+            }
+        }
+    };
+}
+
+job_enum_arg! {
+    /// Values of `--overlap`
+    OverlapArg => OverlapPolicy { Skip, Queue, Concurrent, Replace }
+}
+
+job_enum_arg! {
+    /// Values of `--catch-up`
+    CatchUpArg => CatchUpPolicy { Skip, Once, All }
+}
+
+job_enum_arg! {
+    /// Values of `--exec`
+    ExecModeArg => ExecMode { LoginShell, Shell, Direct }
+}
+
+job_enum_arg! {
+    /// Values of `--on-shutdown`
+    ShutdownArg => ShutdownPolicy { Stop, Detach }
+}
+
 /// Run the command-line interface
 pub fn run(cli: Cli) -> Result<()> {
     // Handle commands
@@ -68,9 +580,41 @@ pub fn run(cli: Cli) -> Result<()> {
         Some(Commands::Create {
             command,
             cron_expression,
-        }) => create_job(command, cron_expression),
-        Some(Commands::List) => list_jobs(),
-        Some(Commands::Stop { id }) => stop_job(id),
+            timezone,
+            tags,
+            env,
+            metadata,
+            execution,
+        }) => create_job(
+            command,
+            cron_expression,
+            timezone,
+            tags,
+            env,
+            metadata,
+            execution,
+        ),
+        Some(Commands::List { tag }) => list_jobs(tag),
+        Some(Commands::Show { job, count }) => show_job(job, count),
+        Some(Commands::Stop { selector }) => stop_jobs(selector),
+        Some(Commands::Pause { selector }) => set_jobs_enabled(selector, false),
+        Some(Commands::Resume { selector }) => set_jobs_enabled(selector, true),
+        Some(Commands::Run { job, foreground }) => run_job(job, foreground),
+        Some(Commands::Kill { job }) => kill_runs(job),
+        Some(Commands::Logs {
+            job,
+            lines,
+            stream,
+            follow,
+        }) => show_logs(job, lines, stream, follow),
+        Some(Commands::History { job, limit, failed }) => show_history(job, limit, failed),
+        Some(Commands::Edit { job, changes }) => edit_job(job, changes),
+        Some(Commands::Env { job, action }) => manage_env(job, action),
+        Some(Commands::Next {
+            cron_expression,
+            count,
+            timezone,
+        }) => preview_schedule(cron_expression, count, timezone),
         Some(Commands::Version) => print_version(),
         Some(Commands::Start) => start_daemon(),
         Some(Commands::DaemonStop) => stop_daemon(),
@@ -92,8 +636,36 @@ fn print_version() -> Result<()> {
     Ok(())
 }
 
+/// Print the next fire times of a cron expression
+fn preview_schedule(cron_expression: String, count: usize, timezone: Option<String>) -> Result<()> {
+    // Validate the expression with the same rules used for jobs
+    let timezone = timezone.unwrap_or_else(JobTimeZone::local_name);
+    let schedule = JobSchedule::parse(&cron_expression, &timezone)?;
+
+    // @reboot has no fire times of its own
+    if schedule.is_reboot() {
+        println!("Runs once each time the daemon starts");
+        return Ok(());
+    }
+
+    // Print the fire times
+    for time in schedule.upcoming().take(count) {
+        println!("{}", schedule.time_zone().format(time));
+    }
+
+    Ok(())
+}
+
 /// Create a new cron job
-fn create_job(command: String, cron_expression: String) -> Result<()> {
+fn create_job(
+    command: String,
+    cron_expression: String,
+    timezone: Option<String>,
+    tags: Vec<String>,
+    env: Vec<(String, String)>,
+    metadata: JobMetadata,
+    execution: ExecutionOptions,
+) -> Result<()> {
     // Create the runtime
     let rt = Runtime::new().map_err(|e| {
         CronrError::InitializationError(format!("Failed to create async runtime: {}", e))
@@ -104,13 +676,30 @@ fn create_job(command: String, cron_expression: String) -> Result<()> {
         // Create the job manager
         let job_manager = JobManager::new().await?;
 
+        // Build the job, only looking up the local time zone if none was given
+        let mut job = match timezone {
+            Some(timezone) => {
+                Job::with_time_zone(command.clone(), cron_expression.clone(), timezone)?
+            }
+            None => Job::new(command.clone(), cron_expression.clone())?,
+        };
+        for tag in tags {
+            job.add_tag(tag);
+        }
+        job.env.extend(env);
+        execution.apply(&mut job);
+        metadata.apply(&mut job)?;
+        job.program()?;
+
         // Add the job
-        let id = job_manager
-            .add_job(command.clone(), cron_expression.clone())
-            .await?;
+        let job_name = job.name.clone();
+        let id = job_manager.insert_job(job).await?;
 
         // Print the job ID
         println!("Added job {} with schedule '{}'", id, cron_expression);
+        if let Some(name) = &job_name {
+            println!("Name: {}", name);
+        }
         println!("Command: {}", command);
 
         // Return success and ensure daemon is running to execute jobs
@@ -120,14 +709,16 @@ fn create_job(command: String, cron_expression: String) -> Result<()> {
         if !daemon.is_running() {
             daemon.start()?;
             println!("Started daemon for job execution");
+        } else {
+            notify_daemon(job_manager.config()).await;
         }
 
         Ok(())
     })
 }
 
-/// List all cron jobs
-fn list_jobs() -> Result<()> {
+/// List all cron jobs, or only those with a tag
+fn list_jobs(tag: Option<String>) -> Result<()> {
     // Create the runtime
     let rt = Runtime::new().map_err(|e| {
         CronrError::InitializationError(format!("Failed to create async runtime: {}", e))
@@ -138,8 +729,11 @@ fn list_jobs() -> Result<()> {
         // Load the job manager from existing configuration
         let job_manager = JobManager::load().await?;
 
-        // Get all jobs
-        let jobs = job_manager.get_all_jobs().await;
+        // Get all jobs, keeping only the tagged ones if asked to
+        let mut jobs = job_manager.get_all_jobs().await;
+        if let Some(tag) = &tag {
+            jobs.retain(|_, job| job.has_tag(tag));
+        }
 
         // Check if there are no jobs
         if jobs.is_empty() {
@@ -148,14 +742,22 @@ fn list_jobs() -> Result<()> {
         }
 
         // Print the jobs
-        println!("ID | Schedule       | Command");
-        println!("---|---------------|--------");
+        println!("ID | Status | Schedule       | Next Run                | Command");
+        println!("---|--------|---------------|-------------------------|--------");
 
         let mut sorted_jobs: Vec<_> = jobs.iter().collect();
         sorted_jobs.sort_by_key(|&(id, _)| *id);
 
         for (id, job) in sorted_jobs {
-            println!("{:2} | {:<13} | {}", id, job.cron_expression, job.command);
+            let status = if job.enabled { "active" } else { "paused" };
+            let next_run = match job.next_run() {
+                Some(time) if job.enabled => job.time_zone().format(time),
+                _ => "-".to_string(),
+            };
+            println!(
+                "{:2} | {:<6} | {:<13} | {:<23} | {}",
+                id, status, job.cron_expression, next_run, job.command
+            );
         }
 
         // Return success
@@ -163,8 +765,8 @@ fn list_jobs() -> Result<()> {
     })
 }
 
-/// Stop a cron job
-fn stop_job(id: usize) -> Result<()> {
+/// Show the details of a cron job
+fn show_job(job: String, count: usize) -> Result<()> {
     // Create the runtime
     let rt = Runtime::new().map_err(|e| {
         CronrError::InitializationError(format!("Failed to create async runtime: {}", e))
@@ -175,14 +777,383 @@ fn stop_job(id: usize) -> Result<()> {
         // Load the job manager from existing configuration
         let job_manager = JobManager::load().await?;
 
-        // Get the job (to display information before removing)
+        // Get the job
+        let id = job_manager.resolve_job_id(&job).await?;
         let job = job_manager.get_job(id).await?;
 
-        // Remove the job
-        job_manager.remove_job(id).await?;
+        // Print the job record
+        println!("ID: {}", id);
+        println!("{}", job);
 
-        // Print the job ID
-        println!("Stopped job {} with schedule '{}'", id, job.cron_expression);
+        // Print the upcoming fire times in the job's time zone
+        if count > 0 && !job.is_reboot() {
+            let time_zone = job.time_zone();
+            println!("Upcoming:");
+            for time in job.upcoming(count)? {
+                println!("  {}", time_zone.format(time));
+            }
+        }
+
+        // Return success
+        Ok(())
+    })
+}
+
+/// Stop a cron job, or all jobs with a tag
+fn stop_jobs(selector: JobSelector) -> Result<()> {
+    // Create the runtime
+    let rt = Runtime::new().map_err(|e| {
+        CronrError::InitializationError(format!("Failed to create async runtime: {}", e))
+    })?;
+
+    // Run the async block
+    rt.block_on(async {
+        // Load the job manager from existing configuration
+        let job_manager = JobManager::load().await?;
+
+        // Remove the selected jobs with a single write
+        let ids = selector.resolve(&job_manager).await?;
+        let removed = job_manager.remove_jobs(&ids).await?;
+
+        // Print the removed jobs
+        for (id, job) in &removed {
+            println!("Stopped job {} with schedule '{}'", id, job.cron_expression);
+            println!("Command: {}", job.command);
+        }
+        if let Some(tag) = &selector.tag {
+            println!("Stopped {} job(s) tagged '{}'", removed.len(), tag);
+        }
+
+        // Let the daemon stop the jobs right away
+        notify_daemon(job_manager.config()).await;
+
+        // Return success
+        Ok(())
+    })
+}
+
+/// Pause or resume a cron job, or all jobs with a tag
+fn set_jobs_enabled(selector: JobSelector, enabled: bool) -> Result<()> {
+    // Create the runtime
+    let rt = Runtime::new().map_err(|e| {
+        CronrError::InitializationError(format!("Failed to create async runtime: {}", e))
+    })?;
+
+    // Run the async block
+    rt.block_on(async {
+        // Load the job manager from existing configuration
+        let job_manager = JobManager::load().await?;
+
+        // Flip the jobs' enabled state with a single write
+        let changed = match &selector.job {
+            Some(job) => {
+                let id = job_manager.resolve_job_id(job).await?;
+                vec![(id, job_manager.set_job_enabled(id, enabled).await?)]
+            }
+            None => {
+                let ids = selector.resolve(&job_manager).await?;
+                job_manager.set_jobs_enabled(&ids, enabled).await?
+            }
+        };
+
+        // Print the new state
+        for (id, job) in &changed {
+            if enabled {
+                let next_run = job
+                    .next_run()
+                    .map_or("Never".to_string(), |time| time.to_string());
+                println!("Resumed job {}, next run: {}", id, next_run);
+            } else {
+                println!("Paused job {}", id);
+            }
+            println!("Command: {}", job.command);
+        }
+        if let Some(tag) = &selector.tag {
+            let action = if enabled { "Resumed" } else { "Paused" };
+            println!("{} {} job(s) tagged '{}'", action, changed.len(), tag);
+        }
+
+        // Let the daemon pick up the change right away
+        notify_daemon(job_manager.config()).await;
+
+        // Return success
+        Ok(())
+    })
+}
+
+/// Run a cron job immediately
+fn run_job(job: String, foreground: bool) -> Result<()> {
+    // Create the runtime
+    let rt = Runtime::new().map_err(|e| {
+        CronrError::InitializationError(format!("Failed to create async runtime: {}", e))
+    })?;
+
+    // Run the async block
+    let (id, code) = rt.block_on(async {
+        // Load the job manager from existing configuration
+        let job_manager = JobManager::load().await?;
+
+        // Get the job
+        let id = job_manager.resolve_job_id(&job).await?;
+        let job = job_manager.get_job(id).await?;
+
+        // In foreground mode, stream the output and exit with the job's own exit code
+        if foreground {
+            let status = job
+                .execute(job_manager.config(), id, None, 1, true, None)
+                .await?;
+            #[cfg(unix)]
+            let code = {
+                use std::os::unix::process::ExitStatusExt;
+                status
+                    .code()
+                    .or_else(|| status.signal().map(|signal| 128 + signal))
+                    .unwrap_or(1)
+            };
+            #[cfg(not(unix))]
+            let code = status.code().unwrap_or(1);
+            process::exit(code);
+        }
+        println!("Running job {}: {}", id, job.command);
+
+        // Hand the run to the daemon if it is reachable, so it can be listed and
+        // killed, and wait for it to finish
+        let request = Request::Trigger { job: id };
+        match control::send_and_wait(&job_manager.config().socket_path(), &request).await {
+            Ok(response) => match response.into_result()? {
+                Response::Finished { code } => return Ok((id, code)),
+                response => {
+                    return Err(CronrError::DaemonCommunicationFailed(format!(
+                        "Unexpected response from the daemon: {:?}",
+                        response
+                    )));
+                }
+            },
+            // The daemon never got the request, or is too old to understand it
+            Err(
+                e @ (CronrError::DaemonUnreachable(_) | CronrError::UnsupportedProtocolVersion(_)),
+            ) => {
+                log::debug!("Running job {} here instead of in the daemon: {}", id, e)
+            }
+            // The daemon took the run, so running it here would run it twice
+            Err(e) => return Err(e),
+        }
+
+        // Execute the job the same way the daemon does, leaving its schedule untouched
+        let status = job
+            .execute(job_manager.config(), id, None, 1, false, None)
+            .await?;
+        Ok::<_, CronrError>((id, status.code()))
+    })?;
+
+    // Report the result and point to the logs
+    match code {
+        Some(0) => println!("Job {} exited successfully", id),
+        Some(code) => println!("Job {} exited with status {}", id, code),
+        None => println!("Job {} was terminated by a signal", id),
+    }
+    println!("Output was written to the job's logs.");
+
+    if code == Some(0) {
+        Ok(())
+    } else {
+        Err(CronrError::JobExecutionError(format!(
+            "Job {} did not exit successfully",
+            id
+        )))
+    }
+}
+
+/// Terminate the runs of a job that are in progress in the daemon
+fn kill_runs(job: String) -> Result<()> {
+    // Create the runtime
+    let rt = Runtime::new().map_err(|e| {
+        CronrError::InitializationError(format!("Failed to create async runtime: {}", e))
+    })?;
+
+    // Run the async block
+    rt.block_on(async {
+        // Load the job manager from existing configuration
+        let job_manager = JobManager::load().await?;
+        let id = job_manager.resolve_job_id(&job).await?;
+
+        // Only the daemon knows which runs are in progress
+        let request = Request::Kill { job: id };
+        let response = control::send(&job_manager.config().socket_path(), &request).await?;
+        if let Response::Done { message } = response.into_result()? {
+            println!("{}", message);
+        }
+
+        Ok(())
+    })
+}
+
+/// Show the logs of a cron job
+fn show_logs(job: String, lines: usize, stream: LogStream, follow: bool) -> Result<()> {
+    // Create the runtime
+    let rt = Runtime::new().map_err(|e| {
+        CronrError::InitializationError(format!("Failed to create async runtime: {}", e))
+    })?;
+
+    // Run the async block
+    rt.block_on(async {
+        // Load the job manager from existing configuration
+        let job_manager = JobManager::load().await?;
+        let config = job_manager.config();
+
+        // Find the job
+        let id = job_manager.resolve_job_id(&job).await?;
+
+        // Collect the selected log files
+        let mut paths = Vec::new();
+        if stream != LogStream::Stderr {
+            paths.push(config.stdout_log_path(id));
+        }
+        if stream != LogStream::Stdout {
+            paths.push(config.stderr_log_path(id));
+        }
+
+        // Print the last lines of each log, with a header when showing both
+        let show_headers = paths.len() > 1;
+        for (i, path) in paths.iter().enumerate() {
+            if show_headers {
+                if i > 0 {
+                    println!();
+                }
+                println!("==> {} <==", path.display());
+            }
+            for line in config.log_rotation().tail_lines(path, lines)? {
+                println!("{}", line);
+            }
+        }
+
+        if !follow {
+            return Ok(());
+        }
+
+        // Follow the logs until interrupted
+        let mut followers: Vec<LogFollower> = paths.into_iter().map(LogFollower::new).collect();
+        let mut last_shown = followers.len() - 1;
+        let mut stdout = std::io::stdout();
+        loop {
+            for (i, follower) in followers.iter_mut().enumerate() {
+                let data = follower.poll()?;
+                if data.is_empty() {
+                    continue;
+                }
+
+                // Print a header whenever output switches between logs
+                if show_headers && i != last_shown {
+                    println!("\n==> {} <==", follower.path().display());
+                    last_shown = i;
+                }
+                let _ = stdout.write_all(&data).and_then(|_| stdout.flush());
+            }
+
+            tokio::time::sleep(Duration::from_millis(500)).await;
+        }
+    })
+}
+
+/// Show the run history of a cron job
+fn show_history(job: String, limit: usize, failed: bool) -> Result<()> {
+    // Create the runtime
+    let rt = Runtime::new().map_err(|e| {
+        CronrError::InitializationError(format!("Failed to create async runtime: {}", e))
+    })?;
+
+    // Run the async block
+    rt.block_on(async {
+        // Load the job manager from existing configuration
+        let job_manager = JobManager::load().await?;
+
+        // Get the job, whose time zone the run times are shown in
+        let id = job_manager.resolve_job_id(&job).await?;
+        let time_zone = job_manager.get_job(id).await?.time_zone();
+
+        // Get the requested entries, newest last
+        let mut entries = job_manager.config().history(id).entries()?;
+        if failed {
+            entries.retain(|entry| entry.outcome != RunOutcome::Succeeded);
+        }
+        let skip = entries.len().saturating_sub(limit);
+        let entries = &entries[skip..];
+
+        // Check if there are no runs
+        if entries.is_empty() {
+            println!("No runs recorded for job {}.", id);
+            return Ok(());
+        }
+
+        // Print the entries
+        println!(
+            "Run  | Scheduled               | Started                 | Duration | PID     | Result"
+        );
+        println!(
+            "-----|-------------------------|-------------------------|----------|---------|-------"
+        );
+        for entry in entries {
+            let scheduled = entry
+                .scheduled_at
+                .map_or("manual".to_string(), |time| time_zone.format(time));
+            let pid = entry.pid.map_or("-".to_string(), |pid| pid.to_string());
+            let mut result = entry.result_summary();
+            if entry.attempt > 1 {
+                result.push_str(&format!(" (attempt {})", entry.attempt));
+            }
+            println!(
+                "{:<4} | {:<23} | {:<23} | {:>8} | {:<7} | {}",
+                entry.run_id,
+                scheduled,
+                time_zone.format(entry.started_at),
+                format_duration(entry.duration_ms),
+                pid,
+                result
+            );
+        }
+
+        // Return success
+        Ok(())
+    })
+}
+
+/// Format a duration in milliseconds for display
+fn format_duration(ms: u64) -> String {
+    if ms < 1000 {
+        format!("{}ms", ms)
+    } else if ms < 60_000 {
+        format!("{:.1}s", ms as f64 / 1000.0)
+    } else {
+        let secs = ms / 1000;
+        format!("{}m{:02}s", secs / 60, secs % 60)
+    }
+}
+
+/// Edit a cron job
+fn edit_job(job: String, changes: JobChanges) -> Result<()> {
+    // Create the runtime
+    let rt = Runtime::new().map_err(|e| {
+        CronrError::InitializationError(format!("Failed to create async runtime: {}", e))
+    })?;
+
+    // Run the async block
+    rt.block_on(async {
+        // Load the job manager from existing configuration
+        let job_manager = JobManager::load().await?;
+
+        // Get the current job definition
+        let id = job_manager.resolve_job_id(&job).await?;
+        let mut job = job_manager.get_job(id).await?;
+
+        // Apply the changes
+        changes.apply(&mut job)?;
+
+        // Save the job under the same ID and let the daemon restart it
+        job_manager.update_job(id, job.clone()).await?;
+        notify_daemon(job_manager.config()).await;
+
+        // Print the updated job
+        println!("Updated job {} with schedule '{}'", id, job.cron_expression);
         println!("Command: {}", job.command);
 
         // Return success
@@ -190,6 +1161,136 @@ fn stop_job(id: usize) -> Result<()> {
     })
 }
 
+/// List, set or unset a job's environment variables
+fn manage_env(job: String, action: EnvAction) -> Result<()> {
+    // Create the runtime
+    let rt = Runtime::new().map_err(|e| {
+        CronrError::InitializationError(format!("Failed to create async runtime: {}", e))
+    })?;
+
+    // Run the async block
+    rt.block_on(async {
+        // Load the job manager from existing configuration
+        let job_manager = JobManager::load().await?;
+        let id = job_manager.resolve_job_id(&job).await?;
+        let mut job = job_manager.get_job(id).await?;
+
+        match action {
+            EnvAction::List => {
+                // Print the variables sorted by name
+                let mut vars: Vec<_> = job.env.iter().collect();
+                vars.sort_unstable();
+                for (key, value) in vars {
+                    println!("{}={}", key, value);
+                }
+                return Ok(());
+            }
+            EnvAction::Set { vars } => {
+                for (key, value) in vars {
+                    println!("Set {} for job {}", key, id);
+                    job.env.insert(key, value);
+                }
+            }
+            EnvAction::Unset { keys } => {
+                for key in keys {
+                    if job.env.remove(&key).is_some() {
+                        println!("Unset {} for job {}", key, id);
+                    } else {
+                        println!("{} is not set for job {}", key, id);
+                    }
+                }
+            }
+        }
+
+        // Save the job and let the daemon restart it with the new environment
+        job_manager.update_job(id, job).await?;
+        notify_daemon(job_manager.config()).await;
+        Ok(())
+    })
+}
+
+/// Ask the daemon to reload the jobs now. If it can't be reached, it picks
+/// up the change on its next periodic reload instead.
+async fn notify_daemon(config: &Config) {
+    let result = control::send(&config.socket_path(), &Request::Reload)
+        .await
+        .and_then(Response::into_result);
+    if let Err(e) = result {
+        log::debug!(
+            "Daemon not notified, the change is picked up on its next reload: {}",
+            e
+        );
+    }
+}
+
+/// Parse a job tag
+fn parse_tag(s: &str) -> std::result::Result<String, String> {
+    if s.is_empty() || s.chars().any(|c| c.is_whitespace() || c == ',') {
+        return Err(format!(
+            "invalid tag '{}', tags can't be empty or contain whitespace or commas",
+            s
+        ));
+    }
+    Ok(s.to_string())
+}
+
+/// Parse a working directory, making it absolute so it doesn't depend on
+/// where the daemon runs
+fn parse_working_dir(s: &str) -> std::result::Result<PathBuf, String> {
+    let dir = std::path::absolute(s).map_err(|e| format!("invalid directory '{}': {}", s, e))?;
+    if !dir.is_dir() {
+        return Err(format!("directory '{}' does not exist", dir.display()));
+    }
+    Ok(dir)
+}
+
+/// Parse a duration given in seconds or with an s, m, h or d suffix into seconds
+fn parse_duration(s: &str) -> std::result::Result<u64, String> {
+    let (number, unit) = match s.find(|c: char| !c.is_ascii_digit()) {
+        Some(pos) => s.split_at(pos),
+        None => (s, "s"),
+    };
+    let multiplier = match unit {
+        "s" => 1,
+        "m" => 60,
+        "h" => 60 * 60,
+        "d" => 24 * 60 * 60,
+        _ => {
+            return Err(format!(
+                "invalid duration '{}', expected e.g. 90s, 30m or 2h",
+                s
+            ));
+        }
+    };
+    number
+        .parse::<u64>()
+        .ok()
+        .and_then(|n| n.checked_mul(multiplier))
+        .ok_or_else(|| format!("invalid duration '{}', expected e.g. 90s, 30m or 2h", s))
+}
+
+/// Parse a retry backoff multiplier
+fn parse_backoff(s: &str) -> std::result::Result<f64, String> {
+    match s.parse::<f64>() {
+        Ok(factor) if factor.is_finite() && factor >= 1.0 => Ok(factor),
+        _ => Err(format!(
+            "invalid backoff '{}', expected a number of at least 1",
+            s
+        )),
+    }
+}
+
+/// Parse a KEY=VALUE environment variable assignment
+fn parse_env_var(s: &str) -> std::result::Result<(String, String), String> {
+    match s.split_once('=') {
+        Some((key, value)) if !key.is_empty() => Ok((key.to_string(), value.to_string())),
+        _ => Err(format!(
+            "invalid environment variable '{}', expected KEY=VALUE",
+            s
+        )),
+    }
+}
+
 /// Start the daemon
 fn start_daemon() -> Result<()> {
     // Create the runtime
@@ -247,8 +1348,21 @@ fn stop_daemon() -> Result<()> {
             return Ok(());
         }
 
-        // Stop the daemon
-        daemon.stop()?;
+        // Stop the daemon, allowing for its runs in progress to get their
+        // grace period and then the longest time a job's runs get to exit
+        let grace = job_manager
+            .config()
+            .settings()
+            .unwrap_or_default()
+            .shutdown_grace();
+        let kill_grace = job_manager
+            .get_all_jobs()
+            .await
+            .values()
+            .map(Job::kill_grace)
+            .max()
+            .unwrap_or_default();
+        daemon.stop(grace + kill_grace + STOP_MARGIN)?;
 
         // Print the status
         println!("Stopped daemon.");
@@ -273,24 +1387,134 @@ fn check_daemon_status() -> Result<()> {
             Err(CronrError::ConfigError(_)) => JobManager::new().await?,
             Err(e) => return Err(e),
         };
-
-        // Get active job count
-        let active_count = job_manager.get_all_jobs().await.len();
+        let config = job_manager.config();
+        let mut jobs: Vec<_> = job_manager.get_all_jobs().await.into_iter().collect();
+        jobs.sort_by_key(|(id, _)| *id);
 
         // Print version
         println!("cronr version: {}", env!("CARGO_PKG_VERSION"));
 
         // Print number of active jobs
-        println!("Active jobs: {}", active_count);
-
-        // Create the daemon
-        let daemon = Daemon::new(job_manager.config().data_dir().to_path_buf());
+        println!("Active jobs: {}", jobs.len());
+
+        // Print the space the logs and history take up
+        println!(
+            "Data directory: {} ({})",
+            config.data_dir().display(),
+            format_size(dir_size(config.data_dir()))
+        );
+
+        // Ask the daemon for its state and the runs in progress
+        let daemon = Daemon::new(config.data_dir().to_path_buf());
+        let running = daemon.is_running();
+        let reply = if running {
+            Some(control::send(&config.socket_path(), &Request::Health).await)
+        } else {
+            None
+        };
+        let health = match &reply {
+            Some(Ok(Response::Health { daemon, jobs })) => Some((daemon, jobs)),
+            _ => None,
+        };
 
         // Print daemon status
-        if daemon.is_running() {
-            println!("Daemon is running.");
-        } else {
-            println!("Daemon is not running.");
+        let pid = daemon.pid().map_or("?".to_string(), |pid| pid.to_string());
+        match &reply {
+            None => println!("Daemon is not running."),
+            Some(Ok(Response::Health { daemon: status, .. })) => {
+                let uptime = (Utc::now() - status.started_at).num_seconds().max(0);
+                println!("Daemon is running (PID {}).", status.pid);
+                println!(
+                    "Started: {} (up {})",
+                    status.started_at.format("%Y-%m-%d %H:%M:%S UTC"),
+                    format_uptime(uptime as u64)
+                );
+            }
+            Some(Err(CronrError::UnsupportedProtocolVersion(version))) => {
+                // The daemon was started by another version of cronr
+                println!(
+                    "Daemon is running (PID {}), but speaks control protocol version {} \
+                     instead of {}. Restart it to use this version of cronr.",
+                    pid,
+                    version,
+                    control::PROTOCOL_VERSION
+                );
+            }
+            Some(_) => {
+                println!(
+                    "Daemon is running (PID {}), but not answering on its control socket.",
+                    pid
+                );
+            }
+        }
+
+        // Print the runs in progress
+        let statuses = health.as_ref().map_or(&[][..], |(_, jobs)| jobs.as_slice());
+        if health.is_some() {
+            let runs: Vec<_> = statuses.iter().flat_map(|job| &job.runs).collect();
+            println!("Runs in progress: {}", runs.len());
+            for run in runs {
+                let pid = run.pid.map_or("?".to_string(), |pid| pid.to_string());
+                let elapsed = (Utc::now() - run.started_at).num_milliseconds().max(0);
+                println!(
+                    "  Job {} (PID {}) running for {}",
+                    run.job_id,
+                    pid,
+                    format_duration(elapsed as u64)
+                );
+            }
+        }
+
+        // Print each job's state, last result and next run
+        if !jobs.is_empty() {
+            println!();
+            println!(
+                "ID | State    | Next Run                | Last Run                | Last Result"
+            );
+            println!(
+                "---|----------|-------------------------|-------------------------|------------"
+            );
+        }
+        for (id, job) in &jobs {
+            let status = statuses.iter().find(|status| status.id == *id);
+            let state = match status {
+                Some(status) if !status.runs.is_empty() => "running",
+                _ if !job.enabled => "paused",
+                Some(status) if !status.scheduled && !job.is_reboot() => "stopped",
+                _ => "active",
+            };
+
+            let time_zone = job.time_zone();
+            let next_run = match job.next_run() {
+                Some(time) if job.enabled => time_zone.format(time),
+                _ => "-".to_string(),
+            };
+            // A job whose history can't be read shows the error instead of failing the status
+            let (last_run, last_result) = match config.history(*id).entries() {
+                Ok(mut entries) => match entries.pop() {
+                    Some(entry) => (time_zone.format(entry.started_at), entry.result_summary()),
+                    None => ("-".to_string(), "-".to_string()),
+                },
+                Err(e) => ("-".to_string(), e.to_string()),
+            };
+            println!(
+                "{:2} | {:<8} | {:<23} | {:<23} | {}",
+                id, state, next_run, last_run, last_result
+            );
+        }
+
+        // Print the executors the daemon had to restart
+        if let Some((status, _)) = &health {
+            println!();
+            println!("Executor restarts: {}", status.executor_restarts.len());
+            for restart in &status.executor_restarts {
+                println!(
+                    "  Job {} at {}: {}",
+                    restart.job_id,
+                    restart.at.format("%Y-%m-%d %H:%M:%S UTC"),
+                    restart.reason
+                );
+            }
         }
 
         // Return success
@@ -298,6 +1522,50 @@ fn check_daemon_status() -> Result<()> {
     })
 }
 
+/// Add up the sizes of the files in a directory and its subdirectories
+fn dir_size(dir: &Path) -> u64 {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return 0;
+    };
+    entries
+        .flatten()
+        .map(|entry| match entry.metadata() {
+            Ok(metadata) if metadata.is_dir() => dir_size(&entry.path()),
+            Ok(metadata) => metadata.len(),
+            Err(_) => 0,
+        })
+        .sum()
+}
+
+/// Format a size in bytes for display
+fn format_size(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["KiB", "MiB", "GiB", "TiB"];
+    if bytes < 1024 {
+        return format!("{} B", bytes);
+    }
+    let mut size = bytes as f64 / 1024.0;
+    let mut unit = 0;
+    while size >= 1024.0 && unit < UNITS.len() - 1 {
+        size /= 1024.0;
+        unit += 1;
+    }
+    format!("{:.1} {}", size, UNITS[unit])
+}
+
+/// Format how long the daemon has been up for display
+fn format_uptime(secs: u64) -> String {
+    let (days, hours, mins) = (secs / 86_400, secs / 3600 % 24, secs / 60 % 60);
+    if days > 0 {
+        format!("{}d {}h {}m", days, hours, mins)
+    } else if hours > 0 {
+        format!("{}h {}m", hours, mins)
+    } else if mins > 0 {
+        format!("{}m {}s", mins, secs % 60)
+    } else {
+        format!("{}s", secs)
+    }
+}
+
 /// Run the daemon internal process
 fn run_daemon_internal() -> Result<()> {
     // Create the runtime
@@ -1,14 +1,19 @@
-use dirs;
+use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::fs::{self, File};
 use std::io::{BufReader, BufWriter, Write};
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
+use std::time::Duration;
 use tokio::sync::Mutex;
 
 use crate::errors::{CronrError, Result, path_error_to_config_error};
+use crate::history::History;
 use crate::job::Job;
+use crate::limits::ConcurrencyLimiter;
 use crate::logger::LogRotation;
+use crate::runs::ActiveRuns;
+use crate::watcher::KnownContents;
 
 /// Configuration for the cron manager
 #[derive(Debug, Clone)]
@@ -18,6 +23,45 @@ pub struct Config {
 
     /// Log rotation configuration
     log_rotation: LogRotation,
+
+    /// Limits on the number of job commands running at once
+    limiter: ConcurrencyLimiter,
+
+    /// The job commands in progress
+    active_runs: ActiveRuns,
+
+    /// The contents of the jobs file this process last loaded or wrote itself
+    known_jobs: KnownContents,
+}
+
+/// Daemon-wide settings, read from config.json in the data directory
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct DaemonSettings {
+    /// Maximum number of job commands running at once (None for no limit)
+    #[serde(default)]
+    pub max_concurrent_runs: Option<usize>,
+
+    /// Maximum number of job commands running at once per concurrency group
+    #[serde(default)]
+    pub concurrency_groups: HashMap<String, usize>,
+
+    /// How long in seconds the daemon waits for runs in progress to finish
+    /// when shutting down, before terminating them (None for the default)
+    #[serde(default)]
+    pub shutdown_grace_secs: Option<u64>,
+}
+
+/// Default time the daemon waits for runs in progress when shutting down
+pub const DEFAULT_SHUTDOWN_GRACE_SECS: u64 = 30;
+
+impl DaemonSettings {
+    /// Get how long to wait for runs in progress when shutting down
+    pub fn shutdown_grace(&self) -> Duration {
+        Duration::from_secs(
+            self.shutdown_grace_secs
+                .unwrap_or(DEFAULT_SHUTDOWN_GRACE_SECS),
+        )
+    }
 }
 
 impl Config {
@@ -39,6 +83,9 @@ impl Config {
         Ok(Config {
             data_dir,
             log_rotation,
+            limiter: ConcurrencyLimiter::default(),
+            active_runs: ActiveRuns::default(),
+            known_jobs: KnownContents::default(),
         })
     }
 
@@ -61,6 +108,9 @@ impl Config {
         Ok(Config {
             data_dir,
             log_rotation,
+            limiter: ConcurrencyLimiter::default(),
+            active_runs: ActiveRuns::default(),
+            known_jobs: KnownContents::default(),
         })
     }
 
@@ -93,6 +143,9 @@ impl Config {
         Ok(Config {
             data_dir,
             log_rotation,
+            limiter: ConcurrencyLimiter::default(),
+            active_runs: ActiveRuns::default(),
+            known_jobs: KnownContents::default(),
         })
     }
 
@@ -101,6 +154,82 @@ impl Config {
         &self.data_dir
     }
 
+    /// Get the daemon settings file path
+    pub fn settings_file(&self) -> PathBuf {
+        self.data_dir.join("config.json")
+    }
+
+    /// Read the daemon settings, using the defaults if there is no settings file
+    pub fn settings(&self) -> Result<DaemonSettings> {
+        let settings_file = self.settings_file();
+        if !settings_file.exists() {
+            return Ok(DaemonSettings::default());
+        }
+
+        let file = File::open(&settings_file)
+            .map_err(|e| path_error_to_config_error(&settings_file, e))?;
+        let settings: DaemonSettings =
+            serde_json::from_reader(BufReader::new(file)).map_err(|e| {
+                CronrError::ConfigError(format!("Failed to parse settings file: {}", e))
+            })?;
+
+        // A limit of 0 would keep runs waiting forever
+        if settings.max_concurrent_runs == Some(0) {
+            return Err(CronrError::ConfigError(
+                "max_concurrent_runs must be at least 1".to_string(),
+            ));
+        }
+        if let Some((group, _)) = settings
+            .concurrency_groups
+            .iter()
+            .find(|(_, max)| **max == 0)
+        {
+            return Err(CronrError::ConfigError(format!(
+                "The limit of concurrency group '{}' must be at least 1",
+                group
+            )));
+        }
+
+        Ok(settings)
+    }
+
+    /// Use the given limiter for the commands run with this configuration
+    pub fn with_limiter(mut self, limiter: ConcurrencyLimiter) -> Self {
+        self.limiter = limiter;
+        self
+    }
+
+    /// Get the limiter for the commands run with this configuration
+    pub fn limiter(&self) -> &ConcurrencyLimiter {
+        &self.limiter
+    }
+
+    /// Track the commands run with this configuration in the given registry
+    pub fn with_active_runs(mut self, active_runs: ActiveRuns) -> Self {
+        self.active_runs = active_runs;
+        self
+    }
+
+    /// Get the registry of commands in progress run with this configuration
+    pub fn active_runs(&self) -> &ActiveRuns {
+        &self.active_runs
+    }
+
+    /// Get the record of the jobs file contents this process already knows
+    pub fn known_jobs(&self) -> &KnownContents {
+        &self.known_jobs
+    }
+
+    /// Get the path of the daemon's control socket
+    pub fn socket_path(&self) -> PathBuf {
+        Self::socket_path_in(&self.data_dir)
+    }
+
+    /// Get the path of the control socket of a daemon using the given data directory
+    pub fn socket_path_in(data_dir: &Path) -> PathBuf {
+        data_dir.join("cronr.sock")
+    }
+
     /// Get the jobs file path
     pub fn jobs_file(&self) -> PathBuf {
         self.data_dir.join("jobs.json")
@@ -125,10 +254,26 @@ impl Config {
         &self.log_rotation
     }
 
+    /// Get the run history path for a job
+    pub fn history_path(&self, job_id: usize) -> PathBuf {
+        self.data_dir
+            .join("history")
+            .join(format!("{}.jsonl", job_id))
+    }
+
+    /// Get the run history of a job, keeping the last 100 runs
+    pub fn history(&self, job_id: usize) -> History {
+        History::new(self.history_path(job_id), 100)
+    }
+
     /// Update a single job's persisted state (next_run, last_executed) in the jobs file.
     /// This is called from the job executor after each run to keep the on-disk state
     /// in sync with the in-memory state, so that daemon reload cycles and restarts
     /// see accurate schedule information.
+    ///
+    /// Only the runtime fields are written, so a job edited on disk while it was running
+    /// keeps its new definition. If the schedule was changed in the meantime, the stale
+    /// next run time is not written either.
     pub fn update_job_state(&self, job_id: usize, job: &crate::job::Job) -> Result<()> {
         let jobs_file = self.jobs_file();
 
@@ -137,11 +282,14 @@ impl Config {
             return Ok(());
         }
 
-        // Read the current jobs file
-        let file =
-            File::open(&jobs_file).map_err(|e| path_error_to_config_error(&jobs_file, e))?;
-        let reader = BufReader::new(file);
-        let mut value: serde_json::Value = serde_json::from_reader(reader)
+        // Read the current jobs file, noting whether anyone else changed it
+        // since this process last saw it. Other updates from this process wait
+        // until this one is written.
+        let mut known = self.known_jobs.lock();
+        let contents =
+            fs::read(&jobs_file).map_err(|e| path_error_to_config_error(&jobs_file, e))?;
+        let was_known = known.is_known(&contents);
+        let mut value: serde_json::Value = serde_json::from_slice(&contents)
             .map_err(|e| CronrError::ConfigError(format!("Failed to parse jobs file: {}", e)))?;
 
         // Navigate to the correct job entry (supports both new and legacy format)
@@ -152,11 +300,21 @@ impl Config {
             &mut value
         };
 
-        // Serialize the updated job and replace the entry
+        // Patch the runtime fields of the entry, leaving its definition untouched
         if let Some(job_value) = jobs_obj.get_mut(&id_str) {
-            let job_json = serde_json::to_value(job)
-                .map_err(|e| CronrError::ConfigError(format!("Failed to serialize job: {}", e)))?;
-            *job_value = job_json;
+            let to_json = |value| {
+                serde_json::to_value(value).map_err(|e| {
+                    CronrError::ConfigError(format!("Failed to serialize job state: {}", e))
+                })
+            };
+            let same_schedule = job_value
+                .get("cron_expression")
+                .and_then(|v| v.as_str())
+                .is_some_and(|expr| expr == job.cron_expression);
+            job_value["last_executed"] = to_json(job.last_executed)?;
+            if same_schedule {
+                job_value["next_run"] = to_json(job.next_run)?;
+            }
         }
 
         // Write back atomically via a temp file + rename
@@ -164,17 +322,18 @@ impl Config {
             "{}.tmp",
             jobs_file.file_name().unwrap().to_string_lossy()
         ));
-        let file =
-            File::create(&temp_file).map_err(|e| path_error_to_config_error(&temp_file, e))?;
-        let mut writer = BufWriter::new(file);
-        serde_json::to_writer_pretty(&mut writer, &value)
+        let contents = serde_json::to_vec_pretty(&value)
             .map_err(|e| CronrError::ConfigError(format!("Failed to write jobs file: {}", e)))?;
-        writer
-            .flush()
-            .map_err(|e| CronrError::ConfigError(format!("Failed to flush jobs file: {}", e)))?;
+        fs::write(&temp_file, &contents).map_err(|e| path_error_to_config_error(&temp_file, e))?;
         fs::rename(&temp_file, &jobs_file)
             .map_err(|e| path_error_to_config_error(&jobs_file, e))?;
 
+        // The daemon doesn't need to reload its own state updates. Changes made
+        // by others before this write stay unknown, so they are still reloaded.
+        if was_known {
+            known.record(&contents);
+        }
+
         Ok(())
     }
 }
@@ -237,16 +396,36 @@ impl JobManager {
         })
     }
 
+    /// Load the jobs from disk again, keeping the configuration
+    pub async fn reload(&self) -> Result<Self> {
+        let config = self.config.clone();
+        let (jobs, next_id) = Self::load_jobs(&config).await?;
+
+        Ok(JobManager {
+            config,
+            jobs: Arc::new(Mutex::new(jobs)),
+            next_id: Arc::new(Mutex::new(next_id)),
+        })
+    }
+
     /// Get the configuration
     pub fn config(&self) -> &Config {
         &self.config
     }
 
     /// Add a new job
+    /// This is used only in tests
+    #[cfg(test)]
     pub async fn add_job(&self, command: String, cron_expression: String) -> Result<usize> {
         // Create the job
         let job = Job::new(command, cron_expression)?;
 
+        // Add it under a new ID
+        self.insert_job(job).await
+    }
+
+    /// Add an already configured job under a new ID
+    pub async fn insert_job(&self, job: Job) -> Result<usize> {
         // Get the next ID
         let id = {
             let mut next_id = self.next_id.lock().await;
@@ -258,6 +437,7 @@ impl JobManager {
         // Add the job
         {
             let mut jobs = self.jobs.lock().await;
+            check_unique_name(&jobs, id, &job)?;
             jobs.insert(id, job);
         }
 
@@ -273,9 +453,28 @@ impl JobManager {
         let jobs = self.jobs.lock().await;
 
         // Get the job
-        jobs.get(&id)
-            .cloned()
-            .ok_or_else(|| CronrError::InvalidJobId(id))
+        jobs.get(&id).cloned().ok_or(CronrError::InvalidJobId(id))
+    }
+
+    /// Find the ID of a job given either its ID or its name
+    pub async fn resolve_job_id(&self, id_or_name: &str) -> Result<usize> {
+        // Get the jobs
+        let jobs = self.jobs.lock().await;
+
+        // Numbers are always job IDs, since names can't be numeric
+        if let Ok(id) = id_or_name.parse::<usize>() {
+            return if jobs.contains_key(&id) {
+                Ok(id)
+            } else {
+                Err(CronrError::InvalidJobId(id))
+            };
+        }
+
+        // Otherwise look the job up by name
+        jobs.iter()
+            .find(|(_, job)| job.name.as_deref() == Some(id_or_name))
+            .map(|(id, _)| *id)
+            .ok_or_else(|| CronrError::JobNotFound(id_or_name.to_string()))
     }
 
     /// Get all jobs
@@ -288,26 +487,131 @@ impl JobManager {
     }
 
     /// Update a job
-    // pub async fn update_job(&self, id: usize, job: Job) -> Result<()> {
-    // 	// Get the jobs
-    // 	let mut jobs = self.jobs.lock().await;
+    pub async fn update_job(&self, id: usize, job: Job) -> Result<()> {
+        // Get the jobs
+        let mut jobs = self.jobs.lock().await;
+
+        // Check if the job exists
+        if !jobs.contains_key(&id) {
+            return Err(CronrError::InvalidJobId(id));
+        }
+
+        // Update the job
+        check_unique_name(&jobs, id, &job)?;
+        jobs.insert(id, job);
+
+        // Save the jobs
+        drop(jobs);
+        self.save_jobs().await?;
+
+        Ok(())
+    }
+
+    /// Enable or disable a job, returning the updated job
+    pub async fn set_job_enabled(&self, id: usize, enabled: bool) -> Result<Job> {
+        // Get the jobs
+        let mut jobs = self.jobs.lock().await;
+
+        // Get the job
+        let job = jobs.get_mut(&id).ok_or(CronrError::InvalidJobId(id))?;
+
+        // Flip the enabled state
+        if enabled {
+            job.enable();
+        } else {
+            job.disable();
+        }
+        let job = job.clone();
+
+        // Save the jobs
+        drop(jobs);
+        self.save_jobs().await?;
+
+        Ok(job)
+    }
+
+    /// Get the IDs of all jobs with a tag, in order
+    pub async fn job_ids_with_tag(&self, tag: &str) -> Vec<usize> {
+        // Get the jobs
+        let jobs = self.jobs.lock().await;
+
+        // Select the tagged jobs
+        let mut ids: Vec<usize> = jobs
+            .iter()
+            .filter(|(_, job)| job.has_tag(tag))
+            .map(|(id, _)| *id)
+            .collect();
+        ids.sort_unstable();
+        ids
+    }
+
+    /// Enable or disable several jobs with a single write, returning the jobs
+    /// whose state changed
+    pub async fn set_jobs_enabled(
+        &self,
+        ids: &[usize],
+        enabled: bool,
+    ) -> Result<Vec<(usize, Job)>> {
+        // Get the jobs
+        let mut jobs = self.jobs.lock().await;
+
+        // Check that all jobs exist before changing any of them
+        if let Some(id) = ids.iter().find(|id| !jobs.contains_key(id)) {
+            return Err(CronrError::InvalidJobId(*id));
+        }
+
+        // Flip the jobs that aren't in the requested state yet
+        let mut changed = Vec::new();
+        for id in ids {
+            let job = jobs.get_mut(id).unwrap();
+            if job.enabled == enabled {
+                continue;
+            }
+            if enabled {
+                job.enable();
+            } else {
+                job.disable();
+            }
+            changed.push((*id, job.clone()));
+        }
+
+        // Save the jobs
+        drop(jobs);
+        if !changed.is_empty() {
+            self.save_jobs().await?;
+        }
+
+        Ok(changed)
+    }
+
+    /// Remove several jobs with a single write, returning the removed jobs
+    pub async fn remove_jobs(&self, ids: &[usize]) -> Result<Vec<(usize, Job)>> {
+        // Get the jobs
+        let mut jobs = self.jobs.lock().await;
 
-    // 	// Check if the job exists
-    // 	if !jobs.contains_key(&id) {
-    // 		return Err(CronrError::InvalidJobId(id));
-    // 	}
+        // Check that all jobs exist before removing any of them
+        if let Some(id) = ids.iter().find(|id| !jobs.contains_key(id)) {
+            return Err(CronrError::InvalidJobId(*id));
+        }
 
-    // 	// Update the job
-    // 	jobs.insert(id, job);
+        // Remove the jobs
+        let removed: Vec<(usize, Job)> = ids
+            .iter()
+            .filter_map(|id| jobs.remove(id).map(|job| (*id, job)))
+            .collect();
 
-    // 	// Save the jobs
-    // 	drop(jobs);
-    // 	self.save_jobs().await?;
+        // Save the jobs
+        drop(jobs);
+        if !removed.is_empty() {
+            self.save_jobs().await?;
+        }
 
-    // 	Ok(())
-    // }
+        Ok(removed)
+    }
 
     /// Remove a job
+    /// This is used only in tests
+    #[cfg(test)]
     pub async fn remove_job(&self, id: usize) -> Result<()> {
         // Get the jobs
         let mut jobs = self.jobs.lock().await;
@@ -337,12 +641,17 @@ impl JobManager {
             return Ok((HashMap::new(), 0));
         }
 
-        // Open and read the file
-        let file = File::open(&jobs_file).map_err(|e| path_error_to_config_error(&jobs_file, e))?;
-        let reader = BufReader::new(file);
+        // Read the file, remembering the contents the jobs were loaded from
+        let contents = {
+            let mut known = config.known_jobs().lock();
+            let contents =
+                fs::read(&jobs_file).map_err(|e| path_error_to_config_error(&jobs_file, e))?;
+            known.record(&contents);
+            contents
+        };
 
         // Parse JSON into a value
-        let value: serde_json::Value = serde_json::from_reader(reader)
+        let value: serde_json::Value = serde_json::from_slice(&contents)
             .map_err(|e| CronrError::ConfigError(format!("Failed to parse jobs file: {}", e)))?;
 
         // Determine if JSON includes metadata
@@ -431,6 +740,24 @@ impl JobManager {
     }
 }
 
+/// Make sure no other job already uses the name of the job stored under `id`
+fn check_unique_name(jobs: &HashMap<usize, Job>, id: usize, job: &Job) -> Result<()> {
+    let Some(name) = &job.name else {
+        return Ok(());
+    };
+
+    match jobs
+        .iter()
+        .find(|(other_id, other)| **other_id != id && other.name.as_ref() == Some(name))
+    {
+        Some((other_id, _)) => Err(CronrError::InvalidJobName(format!(
+            "'{}' is already used by job {}",
+            name, other_id
+        ))),
+        None => Ok(()),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -574,4 +901,254 @@ mod tests {
             "Reloaded next_run should match the persisted value"
         );
     }
+
+    /// Test that update_job replaces the definition under the same ID, and that a later
+    /// update_job_state from an executor running the old definition does not undo it.
+    #[tokio::test]
+    async fn test_update_job_keeps_edit_over_stale_state() {
+        let temp_dir = tempdir().unwrap();
+        let config = Config::with_data_dir(temp_dir.path()).unwrap();
+        let job_manager = JobManager::with_config(config.clone()).await.unwrap();
+
+        let id = job_manager
+            .add_job("echo old".to_string(), "0 * * * * *".to_string())
+            .await
+            .unwrap();
+
+        // The executor still holds the old definition
+        let mut stale = job_manager.get_job(id).await.unwrap();
+
+        // Edit the job
+        let mut edited = stale.clone();
+        edited.command = "echo new".to_string();
+        edited.set_schedule("0 0 * * * *".to_string()).unwrap();
+        job_manager.update_job(id, edited.clone()).await.unwrap();
+
+        // The old executor finishes a run and persists its state
+        stale.set_as_run();
+        config.update_job_state(id, &stale).unwrap();
+
+        // The edit survives, including the next run of the new schedule
+        let reloaded = JobManager::with_config(config).await.unwrap();
+        let job = reloaded.get_job(id).await.unwrap();
+        assert_eq!(job.command(), "echo new");
+        assert_eq!(job.cron_expression(), "0 0 * * * *");
+        assert_eq!(job.next_run(), edited.next_run());
+        assert_eq!(job.last_executed, stale.last_executed);
+
+        // Updating a missing job fails
+        assert!(job_manager.update_job(999, edited).await.is_err());
+    }
+
+    /// Test that the daemon's own state updates keep the jobs file known, while
+    /// changes made by others, before or after them, leave it unknown.
+    #[tokio::test]
+    async fn test_state_updates_keep_jobs_file_known() {
+        let temp_dir = tempdir().unwrap();
+        let config = Config::with_data_dir(temp_dir.path()).unwrap();
+        let job_manager = JobManager::with_config(config.clone()).await.unwrap();
+        let id = job_manager
+            .add_job("echo hello".to_string(), "0 * * * * *".to_string())
+            .await
+            .unwrap();
+
+        // Loading the jobs makes the file known, and state updates keep it so
+        let daemon = job_manager.reload().await.unwrap();
+        let known = daemon.config().known_jobs();
+        assert!(known.is_current(&config.jobs_file()));
+        let mut job = daemon.get_job(id).await.unwrap();
+        job.set_as_run();
+        daemon.config().update_job_state(id, &job).unwrap();
+        assert!(known.is_current(&config.jobs_file()));
+
+        // An edit by someone else stays unknown, even after a state update
+        let other = Config::with_data_dir(temp_dir.path()).unwrap();
+        let mut edited = job.clone();
+        edited.command = "echo edited".to_string();
+        JobManager::with_config(other)
+            .await
+            .unwrap()
+            .update_job(id, edited)
+            .await
+            .unwrap();
+        assert!(!known.is_current(&config.jobs_file()));
+        daemon.config().update_job_state(id, &job).unwrap();
+        assert!(!known.is_current(&config.jobs_file()));
+
+        // Until it is loaded
+        daemon.reload().await.unwrap();
+        assert!(known.is_current(&config.jobs_file()));
+    }
+
+    /// Test that pausing and resuming a job persists the enabled state and
+    /// gives the resumed job a fresh next run time.
+    #[tokio::test]
+    async fn test_set_job_enabled() {
+        let temp_dir = tempdir().unwrap();
+        let config = Config::with_data_dir(temp_dir.path()).unwrap();
+        let job_manager = JobManager::with_config(config.clone()).await.unwrap();
+
+        let id = job_manager
+            .add_job("echo test".to_string(), "0 * * * * *".to_string())
+            .await
+            .unwrap();
+
+        // Pause the job and make its next run stale
+        let mut job = job_manager.set_job_enabled(id, false).await.unwrap();
+        assert!(!job.is_enabled());
+        job.next_run = Some(chrono::Utc::now() - chrono::Duration::hours(1));
+        job_manager.update_job(id, job).await.unwrap();
+
+        // The paused state is persisted
+        let reloaded = JobManager::with_config(config.clone()).await.unwrap();
+        assert!(!reloaded.get_job(id).await.unwrap().is_enabled());
+
+        // Resuming recalculates the next run time
+        let job = reloaded.set_job_enabled(id, true).await.unwrap();
+        assert!(job.is_enabled());
+        assert!(job.next_run().unwrap() > chrono::Utc::now());
+
+        // Pausing a missing job fails
+        assert!(reloaded.set_job_enabled(999, false).await.is_err());
+    }
+
+    /// Test that jobs saved before time zones existed keep their UTC schedule.
+    #[tokio::test]
+    async fn test_load_legacy_job_without_time_zone() {
+        let temp_dir = tempdir().unwrap();
+        let config = Config::with_data_dir(temp_dir.path()).unwrap();
+
+        // Write a jobs file in the legacy format, without a time zone
+        fs::write(
+            config.jobs_file(),
+            r#"{"0": {"command": "echo test", "cron_expression": "0 * * * * *",
+                "enabled": true, "last_executed": null, "next_run": null}}"#,
+        )
+        .unwrap();
+
+        // The job loads and is scheduled in UTC
+        let job_manager = JobManager::with_config(config).await.unwrap();
+        let job = job_manager.get_job(0).await.unwrap();
+        assert_eq!(job.timezone, "UTC");
+        assert!(job.schedule().is_ok());
+        assert_eq!(job.name, None);
+    }
+
+    #[test]
+    fn test_daemon_settings() {
+        let temp_dir = tempdir().unwrap();
+        let config = Config::with_data_dir(temp_dir.path()).unwrap();
+
+        // No settings file means no limits
+        assert_eq!(config.settings().unwrap(), DaemonSettings::default());
+
+        // Settings left out of the file keep their defaults
+        fs::write(
+            config.settings_file(),
+            r#"{"concurrency_groups": {"heavy": 2}}"#,
+        )
+        .unwrap();
+        let settings = config.settings().unwrap();
+        assert_eq!(settings.max_concurrent_runs, None);
+        assert_eq!(settings.concurrency_groups.get("heavy"), Some(&2));
+        assert_eq!(
+            settings.shutdown_grace(),
+            Duration::from_secs(DEFAULT_SHUTDOWN_GRACE_SECS)
+        );
+        fs::write(config.settings_file(), r#"{"shutdown_grace_secs": 5}"#).unwrap();
+        assert_eq!(
+            config.settings().unwrap().shutdown_grace(),
+            Duration::from_secs(5)
+        );
+
+        // A broken file is an error rather than silently unlimited
+        fs::write(config.settings_file(), "{").unwrap();
+        assert!(config.settings().is_err());
+
+        // So are limits that would never let a run start
+        fs::write(config.settings_file(), r#"{"max_concurrent_runs": 0}"#).unwrap();
+        assert!(config.settings().is_err());
+        fs::write(
+            config.settings_file(),
+            r#"{"concurrency_groups": {"heavy": 0}}"#,
+        )
+        .unwrap();
+        assert!(config.settings().is_err());
+    }
+
+    /// Test that jobs can be found by name and that names are unique.
+    #[tokio::test]
+    async fn test_resolve_job_by_name() {
+        let temp_dir = tempdir().unwrap();
+        let config = Config::with_data_dir(temp_dir.path()).unwrap();
+        let job_manager = JobManager::with_config(config.clone()).await.unwrap();
+
+        // Add a named job and an unnamed one
+        let mut job = Job::new("backup.sh".to_string(), "0 0 * * * *".to_string()).unwrap();
+        job.set_name(Some("backup".to_string())).unwrap();
+        job.owner = Some("ops".to_string());
+        let named = job_manager.insert_job(job.clone()).await.unwrap();
+        let unnamed = job_manager
+            .add_job("echo test".to_string(), "0 * * * * *".to_string())
+            .await
+            .unwrap();
+
+        // Jobs resolve by ID or by name, and the metadata is persisted
+        let reloaded = JobManager::with_config(config).await.unwrap();
+        assert_eq!(reloaded.resolve_job_id("backup").await.unwrap(), named);
+        assert_eq!(
+            reloaded.resolve_job_id(&unnamed.to_string()).await.unwrap(),
+            unnamed
+        );
+        assert_eq!(
+            reloaded.get_job(named).await.unwrap().owner.as_deref(),
+            Some("ops")
+        );
+        assert!(reloaded.resolve_job_id("missing").await.is_err());
+        assert!(reloaded.resolve_job_id("999").await.is_err());
+
+        // A second job can't take the same name, but a job can keep its own
+        assert!(reloaded.insert_job(job.clone()).await.is_err());
+        let mut other = reloaded.get_job(unnamed).await.unwrap();
+        other.name = Some("backup".to_string());
+        assert!(reloaded.update_job(unnamed, other).await.is_err());
+        assert!(reloaded.update_job(named, job).await.is_ok());
+    }
+
+    /// Test that bulk operations select jobs by tag and touch only those.
+    #[tokio::test]
+    async fn test_bulk_operations_by_tag() {
+        let temp_dir = tempdir().unwrap();
+        let config = Config::with_data_dir(temp_dir.path()).unwrap();
+        let job_manager = JobManager::with_config(config.clone()).await.unwrap();
+
+        // Add two tagged jobs and an untagged one
+        let mut tagged = Job::new("echo test".to_string(), "0 * * * * *".to_string()).unwrap();
+        tagged.add_tag("legacy".to_string());
+        let first = job_manager.insert_job(tagged.clone()).await.unwrap();
+        let second = job_manager.insert_job(tagged).await.unwrap();
+        let other = job_manager
+            .add_job("echo other".to_string(), "0 * * * * *".to_string())
+            .await
+            .unwrap();
+        let ids = job_manager.job_ids_with_tag("legacy").await;
+        assert_eq!(ids, vec![first, second]);
+
+        // Pausing returns only the jobs that changed
+        job_manager.set_job_enabled(second, false).await.unwrap();
+        let paused = job_manager.set_jobs_enabled(&ids, false).await.unwrap();
+        assert_eq!(paused.len(), 1);
+        assert_eq!(paused[0].0, first);
+
+        // A missing ID fails without changing anything
+        assert!(job_manager.remove_jobs(&[first, 999]).await.is_err());
+        assert_eq!(job_manager.get_all_jobs().await.len(), 3);
+
+        // Removing the tagged jobs leaves the other one, on disk too
+        let removed = job_manager.remove_jobs(&ids).await.unwrap();
+        assert_eq!(removed.len(), 2);
+        let reloaded = JobManager::with_config(config).await.unwrap();
+        let remaining: Vec<usize> = reloaded.get_all_jobs().await.into_keys().collect();
+        assert_eq!(remaining, vec![other]);
+    }
 }
@@ -0,0 +1,403 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+use std::time::Duration;
+use tokio::sync::{mpsc, oneshot};
+
+use crate::errors::{CronrError, Result};
+use crate::runs::ActiveRun;
+
+/// The version of the control protocol. Requests and responses carry it, and
+/// messages with a different version are refused.
+pub const PROTOCOL_VERSION: u32 = 2;
+
+/// How long the CLI waits for the daemon to answer a request
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// A request from the CLI to the daemon
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum Request {
+    /// Reload the jobs and settings from disk now
+    Reload,
+    /// Get the state of one job, or all jobs
+    Status { job: Option<usize> },
+    /// Get the state of the daemon and all jobs
+    Health,
+    /// Run a job now, outside its schedule, answering once the run finishes
+    Trigger { job: usize },
+    /// Terminate the runs of a job that are in progress
+    Kill { job: usize },
+}
+
+/// The daemon's answer to a request
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum Response {
+    /// The request was carried out
+    Done { message: String },
+    /// The state of the requested jobs
+    Status { jobs: Vec<JobStatus> },
+    /// The state of the daemon and all jobs
+    Health {
+        daemon: DaemonStatus,
+        jobs: Vec<JobStatus>,
+    },
+    /// A triggered run finished, with its exit code (None if it was killed by a signal)
+    Finished { code: Option<i32> },
+    /// The request could not be carried out
+    Error { message: String },
+}
+
+/// The state of a job in the daemon
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct JobStatus {
+    /// The job ID
+    pub id: usize,
+
+    /// The job's name (if any)
+    pub name: Option<String>,
+
+    /// The command the job runs
+    pub command: String,
+
+    /// Whether the job is enabled
+    pub enabled: bool,
+
+    /// Whether the daemon is running the job's schedule
+    pub scheduled: bool,
+
+    /// The next fire time (if any)
+    pub next_run: Option<DateTime<Utc>>,
+
+    /// The job's commands that are running
+    pub runs: Vec<ActiveRun>,
+}
+
+/// The state of the daemon process
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DaemonStatus {
+    /// The daemon's PID
+    pub pid: u32,
+
+    /// When the daemon started
+    pub started_at: DateTime<Utc>,
+
+    /// The job executors that stopped unexpectedly and were restarted, oldest first
+    pub executor_restarts: Vec<ExecutorRestart>,
+}
+
+/// A job executor that stopped unexpectedly and was restarted
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ExecutorRestart {
+    /// The job the executor was running
+    pub job_id: usize,
+
+    /// When the executor was found stopped
+    pub at: DateTime<Utc>,
+
+    /// Why the executor stopped
+    pub reason: String,
+}
+
+impl Response {
+    /// Turn an error response into an error
+    pub fn into_result(self) -> Result<Response> {
+        match self {
+            Response::Error { message } => Err(CronrError::CommandExecutionFailed(message)),
+            response => Ok(response),
+        }
+    }
+}
+
+/// A request received on the control socket, with the channel for its response
+pub type ControlRequest = (Request, oneshot::Sender<Response>);
+
+/// Encode a message as one line of JSON tagged with the protocol version
+fn encode<T: Serialize>(message: &T) -> Result<String> {
+    let mut value = serde_json::to_value(message).map_err(|e| {
+        CronrError::DaemonCommunicationFailed(format!("Failed to encode message: {}", e))
+    })?;
+    value["version"] = PROTOCOL_VERSION.into();
+    Ok(format!("{}\n", value))
+}
+
+/// Decode a line of JSON, checking its protocol version first
+fn decode<T: for<'de> Deserialize<'de>>(line: &str) -> Result<T> {
+    let value: serde_json::Value = serde_json::from_str(line)
+        .map_err(|e| CronrError::DaemonCommunicationFailed(format!("Malformed message: {}", e)))?;
+    match value.get("version").and_then(|version| version.as_u64()) {
+        Some(version) if version == u64::from(PROTOCOL_VERSION) => {}
+        version => {
+            return Err(CronrError::UnsupportedProtocolVersion(
+                version.map_or("(none)".to_string(), |version| version.to_string()),
+            ));
+        }
+    }
+    serde_json::from_value(value)
+        .map_err(|e| CronrError::DaemonCommunicationFailed(format!("Malformed message: {}", e)))
+}
+
+/// Listen for requests on the control socket, passing them on to the daemon
+/// through the returned channel. Each connection carries one request and
+/// its response, one JSON line each.
+#[cfg(unix)]
+pub fn serve(socket_path: &Path) -> Result<mpsc::Receiver<ControlRequest>> {
+    use std::fs;
+    use std::os::unix::fs::PermissionsExt;
+    use tokio::net::UnixListener;
+
+    // Remove a socket left behind by a daemon that did not shut down cleanly
+    if socket_path.exists() {
+        fs::remove_file(socket_path)
+            .map_err(|e| crate::errors::path_error_to_config_error(socket_path, e))?;
+    }
+
+    let listener = UnixListener::bind(socket_path).map_err(|e| {
+        CronrError::DaemonCommunicationFailed(format!(
+            "Failed to listen on {}: {}",
+            socket_path.display(),
+            e
+        ))
+    })?;
+
+    // Only the owner may control the daemon
+    fs::set_permissions(socket_path, fs::Permissions::from_mode(0o600))
+        .map_err(|e| crate::errors::path_error_to_config_error(socket_path, e))?;
+
+    let (tx, rx) = mpsc::channel(16);
+    tokio::spawn(async move {
+        loop {
+            match listener.accept().await {
+                Ok((stream, _)) => {
+                    let tx = tx.clone();
+                    tokio::spawn(async move {
+                        if let Err(e) = handle_connection(stream, tx).await {
+                            log::warn!("Control connection failed: {}", e);
+                        }
+                    });
+                }
+                Err(e) => {
+                    log::error!("Failed to accept control connection: {}", e);
+                    tokio::time::sleep(Duration::from_secs(1)).await;
+                }
+            }
+        }
+    });
+
+    Ok(rx)
+}
+
+/// Read one request from a control connection, pass it to the daemon and
+/// write back its response
+#[cfg(unix)]
+async fn handle_connection(
+    stream: tokio::net::UnixStream,
+    requests: mpsc::Sender<ControlRequest>,
+) -> Result<()> {
+    use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+
+    let (reader, mut writer) = stream.into_split();
+    let mut line = String::new();
+    BufReader::new(reader)
+        .read_line(&mut line)
+        .await
+        .map_err(|e| {
+            CronrError::DaemonCommunicationFailed(format!("Failed to read request: {}", e))
+        })?;
+
+    let response = match decode::<Request>(&line) {
+        Ok(request) => {
+            log::debug!("Control request: {:?}", request);
+            let (reply_tx, reply_rx) = oneshot::channel();
+            let sent = requests.send((request, reply_tx)).await;
+            match (sent, reply_rx.await) {
+                (Ok(()), Ok(response)) => response,
+                _ => Response::Error {
+                    message: "The daemon is shutting down".to_string(),
+                },
+            }
+        }
+        Err(e) => Response::Error {
+            message: e.to_string(),
+        },
+    };
+
+    writer
+        .write_all(encode(&response)?.as_bytes())
+        .await
+        .map_err(|e| {
+            CronrError::DaemonCommunicationFailed(format!("Failed to send response: {}", e))
+        })
+}
+
+/// Send a request to the daemon over its control socket and wait for the
+/// response. Fails if the daemon can't be reached.
+pub async fn send(socket_path: &Path, request: &Request) -> Result<Response> {
+    #[cfg(unix)]
+    {
+        let exchange = async { receive(submit(socket_path, request).await?).await };
+        tokio::time::timeout(REQUEST_TIMEOUT, exchange)
+            .await
+            .map_err(|_| timed_out())?
+    }
+
+    #[cfg(not(unix))]
+    {
+        let _ = (socket_path, request, REQUEST_TIMEOUT);
+        Err(unsupported())
+    }
+}
+
+/// Send a request to the daemon over its control socket and wait for the
+/// response for as long as it takes, for requests that are answered once a
+/// run finishes. Fails with `DaemonUnreachable` if the request could not be
+/// delivered, in which case the daemon did not act on it.
+pub async fn send_and_wait(socket_path: &Path, request: &Request) -> Result<Response> {
+    #[cfg(unix)]
+    {
+        let reader = tokio::time::timeout(REQUEST_TIMEOUT, submit(socket_path, request))
+            .await
+            .map_err(|_| {
+                CronrError::DaemonUnreachable("Timed out connecting to the daemon".into())
+            })??;
+        receive(reader).await
+    }
+
+    #[cfg(not(unix))]
+    {
+        let _ = (socket_path, request);
+        Err(unsupported())
+    }
+}
+
+/// The error for a daemon that doesn't answer in time
+#[cfg(unix)]
+fn timed_out() -> CronrError {
+    CronrError::DaemonCommunicationFailed("Timed out waiting for the daemon".into())
+}
+
+/// The error for platforms without a control socket
+#[cfg(not(unix))]
+fn unsupported() -> CronrError {
+    CronrError::DaemonUnreachable("The control socket is only supported on Unix".into())
+}
+
+/// Write a request to the control socket, returning the side of the
+/// connection the response comes back on
+#[cfg(unix)]
+async fn submit(socket_path: &Path, request: &Request) -> Result<tokio::net::unix::OwnedReadHalf> {
+    use tokio::io::AsyncWriteExt;
+    use tokio::net::UnixStream;
+
+    let stream = UnixStream::connect(socket_path).await.map_err(|e| {
+        CronrError::DaemonUnreachable(format!(
+            "Failed to connect to {}: {}",
+            socket_path.display(),
+            e
+        ))
+    })?;
+    let (reader, mut writer) = stream.into_split();
+    writer
+        .write_all(encode(request)?.as_bytes())
+        .await
+        .map_err(|e| CronrError::DaemonUnreachable(format!("Failed to send request: {}", e)))?;
+    Ok(reader)
+}
+
+/// Read the response to a request from the control socket
+#[cfg(unix)]
+async fn receive(reader: tokio::net::unix::OwnedReadHalf) -> Result<Response> {
+    use tokio::io::{AsyncBufReadExt, BufReader};
+
+    let mut line = String::new();
+    BufReader::new(reader)
+        .read_line(&mut line)
+        .await
+        .map_err(|e| {
+            CronrError::DaemonCommunicationFailed(format!("Failed to read response: {}", e))
+        })?;
+    decode(&line)
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_messages_carry_protocol_version() {
+        let line = encode(&Request::Trigger { job: 3 }).unwrap();
+        let value: serde_json::Value = serde_json::from_str(&line).unwrap();
+        assert_eq!(value["version"], PROTOCOL_VERSION);
+        assert_eq!(value["type"], "trigger");
+        assert_eq!(
+            decode::<Request>(&line).unwrap(),
+            Request::Trigger { job: 3 }
+        );
+
+        // Other versions are refused
+        let error = decode::<Request>(r#"{"version": 99, "type": "reload"}"#).unwrap_err();
+        assert!(
+            matches!(error, CronrError::UnsupportedProtocolVersion(version) if version == "99")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_request_round_trip() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let socket_path = temp_dir.path().join("cronr.sock");
+        let mut requests = serve(&socket_path).unwrap();
+
+        // Answer requests the way the daemon would
+        tokio::spawn(async move {
+            while let Some((request, reply)) = requests.recv().await {
+                let message = format!("handled {:?}", request);
+                let _ = reply.send(Response::Done { message });
+            }
+        });
+
+        let response = send(&socket_path, &Request::Kill { job: 7 }).await.unwrap();
+        assert_eq!(
+            response,
+            Response::Done {
+                message: "handled Kill { job: 7 }".to_string()
+            }
+        );
+
+        // Without a daemon listening, sending fails
+        let missing = temp_dir.path().join("missing.sock");
+        assert!(matches!(
+            send(&missing, &Request::Reload).await,
+            Err(CronrError::DaemonUnreachable(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_send_and_wait_tells_undelivered_from_lost() {
+        use tokio::io::{AsyncBufReadExt, BufReader};
+        use tokio::net::UnixListener;
+
+        let temp_dir = tempfile::tempdir().unwrap();
+        let request = Request::Trigger { job: 1 };
+
+        // A request that can't be delivered was never acted on
+        let missing = temp_dir.path().join("missing.sock");
+        assert!(matches!(
+            send_and_wait(&missing, &request).await,
+            Err(CronrError::DaemonUnreachable(_))
+        ));
+
+        // A daemon that goes away after taking the request may have acted on it
+        let socket_path = temp_dir.path().join("cronr.sock");
+        let listener = UnixListener::bind(&socket_path).unwrap();
+        tokio::spawn(async move {
+            let (stream, _) = listener.accept().await.unwrap();
+            let mut line = String::new();
+            BufReader::new(stream).read_line(&mut line).await.unwrap();
+        });
+        assert!(matches!(
+            send_and_wait(&socket_path, &request).await,
+            Err(CronrError::DaemonCommunicationFailed(_))
+        ));
+    }
+}
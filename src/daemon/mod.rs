@@ -1,15 +1,23 @@
-use daemonize::Daemonize;
-use std::collections::HashMap;
+use chrono::{DateTime, Utc};
+use daemonize::{Daemonize, Outcome};
+use std::collections::{HashMap, VecDeque};
+use std::fmt;
 use std::fs::{self, File};
 use std::path::PathBuf;
 use std::process::Command;
-use tokio::sync::watch;
-use tokio::task::JoinHandle;
-use tokio::time::Duration;
-
-use crate::config::JobManager;
+use tokio::sync::{mpsc, oneshot, watch};
+use tokio::task::{JoinHandle, JoinSet};
+use tokio::time::{Duration, Instant};
+
+use crate::config::{Config, DaemonSettings, JobManager};
+use crate::control::{
+    self, ControlRequest, DaemonStatus, ExecutorRestart, JobStatus, Request, Response,
+};
 use crate::errors::{CronrError, Result, path_error_to_config_error};
 use crate::job::{Job, JobExecutor};
+use crate::limits::ConcurrencyLimiter;
+use crate::runs::ActiveRuns;
+use crate::watcher::FileWatcher;
 
 /// The daemon process manager
 pub struct Daemon {
@@ -23,7 +31,7 @@ impl Daemon {
         Daemon { data_dir }
     }
 
-    /// Start the daemon
+    /// Start the daemon, returning once it listens on its control socket
     pub fn start(&self) -> Result<()> {
         // Check if the daemon is already running
         if self.is_running() {
@@ -42,6 +50,14 @@ impl Daemon {
         let stdout =
             File::create(&stdout_file).map_err(|e| path_error_to_config_error(&stdout_file, e))?;
 
+        // Remove a socket left behind by a daemon that did not shut down
+        // cleanly, so it isn't mistaken for the new daemon's
+        let socket_file = self.socket_file();
+        if socket_file.exists() {
+            fs::remove_file(&socket_file)
+                .map_err(|e| path_error_to_config_error(&socket_file, e))?;
+        }
+
         // Log that we're going to start the daemon
         log::info!("Starting daemon process");
 
@@ -57,46 +73,66 @@ impl Daemon {
             .stderr(stdout);
 
         // Start the daemon
-        match daemonize.start() {
-            Ok(_) => {
+        match daemonize.execute() {
+            Outcome::Parent(Ok(parent)) if parent.first_child_exit_code != 0 => {
+                Err(CronrError::DaemonStartFailed(format!(
+                    "Failed to daemonize, the daemon process exited with {}",
+                    parent.first_child_exit_code
+                )))
+            }
+            Outcome::Parent(Ok(_)) => {
+                // Give the daemon time to come up, so it can be reached right away
+                self.wait_until_ready();
+                Ok(())
+            }
+            Outcome::Child(Ok(_)) => {
                 // We're in the daemon process
                 // Run the daemon internal command
                 let exe = std::env::current_exe().map_err(|e| {
                     CronrError::DaemonStartFailed(format!("Failed to get executable path: {}", e))
                 })?;
 
-                let status = Command::new(exe)
-                    .arg("daemon-internal")
-                    .status()
-                    .map_err(|e| {
-                        CronrError::DaemonStartFailed(format!(
-                            "Failed to start daemon process: {}",
-                            e
-                        ))
-                    })?;
-
-                // This should not be reached in the daemon process
-                if !status.success() {
-                    return Err(CronrError::DaemonStartFailed(format!(
-                        "Daemon process exited with status {}",
-                        status.code().unwrap_or(-1)
-                    )));
-                }
+                // Replace this process rather than waiting for a child, so the
+                // PID in the PID file is the daemon's own and signals such as
+                // SIGHUP and SIGTERM reach it directly
+                use std::os::unix::process::CommandExt;
+                let e = Command::new(exe).arg("daemon-internal").exec();
 
-                std::process::exit(0);
+                // exec only returns if it failed
+                Err(CronrError::DaemonStartFailed(format!(
+                    "Failed to start daemon process: {}",
+                    e
+                )))
             }
-            Err(e) => {
+            Outcome::Parent(Err(e)) | Outcome::Child(Err(e)) => {
                 // Failed to start the daemon
-                return Err(CronrError::DaemonStartFailed(format!(
+                Err(CronrError::DaemonStartFailed(format!(
                     "Failed to daemonize: {}",
                     e
-                )));
+                )))
             }
         }
     }
 
-    /// Stop the daemon
-    pub fn stop(&self) -> Result<()> {
+    /// Wait for a starting daemon to listen on its control socket, giving up
+    /// after a while (the daemon keeps running without the socket)
+    fn wait_until_ready(&self) {
+        let deadline = Instant::now() + START_TIMEOUT;
+        while Instant::now() < deadline {
+            if self.socket_file().exists() && self.is_running() {
+                return;
+            }
+            std::thread::sleep(PROCESS_POLL_INTERVAL);
+        }
+        log::warn!(
+            "The daemon did not open its control socket within {}s",
+            START_TIMEOUT.as_secs()
+        );
+    }
+
+    /// Stop the daemon, waiting up to `timeout` for it to exit. The daemon
+    /// gives runs in progress its shutdown grace period to finish first.
+    pub fn stop(&self, timeout: Duration) -> Result<()> {
         // Check if the daemon is running
         if !self.is_running() {
             return Err(CronrError::DaemonStartFailed(
@@ -153,12 +189,42 @@ impl Daemon {
             }
         }
 
+        // Wait for the daemon to finish shutting down, so a new one can't
+        // start alongside it. It removes the PID file itself on exit.
+        #[cfg(any(target_os = "linux", target_os = "macos"))]
+        {
+            let deadline = Instant::now() + timeout;
+            while self.is_running() {
+                if Instant::now() >= deadline {
+                    return Err(CronrError::DaemonStopFailed(format!(
+                        "Daemon (PID {}) is still running {}s after being asked to stop",
+                        pid,
+                        timeout.as_secs()
+                    )));
+                }
+                std::thread::sleep(PROCESS_POLL_INTERVAL);
+            }
+        }
+        #[cfg(not(any(target_os = "linux", target_os = "macos")))]
+        let _ = timeout;
+
         // Remove the PID file
-        fs::remove_file(&pid_file).map_err(|e| path_error_to_config_error(&pid_file, e))?;
+        if pid_file.exists() {
+            fs::remove_file(&pid_file).map_err(|e| path_error_to_config_error(&pid_file, e))?;
+        }
 
         Ok(())
     }
 
+    /// Get the PID the daemon recorded, if its PID file can be read
+    pub fn pid(&self) -> Option<u32> {
+        fs::read_to_string(self.pid_file())
+            .ok()?
+            .trim()
+            .parse()
+            .ok()
+    }
+
     /// Check if the daemon is running
     pub fn is_running(&self) -> bool {
         // Check if the PID file exists
@@ -292,7 +358,13 @@ impl Daemon {
         self.data_dir.join("cronr.pid")
     }
 
+    /// Get the path to the control socket
+    fn socket_file(&self) -> PathBuf {
+        Config::socket_path_in(&self.data_dir)
+    }
+
     /// Register for system startup
+    #[allow(dead_code)]
     pub fn register_for_startup(&self) -> Result<()> {
         #[cfg(target_os = "linux")]
         {
@@ -359,10 +431,185 @@ pub struct DaemonRunner {
 
     /// The job stop signals
     job_stop_signals: HashMap<usize, watch::Sender<bool>>,
+
+    /// The definitions the running jobs were started with
+    job_definitions: HashMap<usize, Job>,
+
+    /// Executors that were told to stop, letting their runs in progress
+    /// finish in the background
+    stopping_jobs: JoinSet<()>,
+
+    /// The limits on commands running at once, shared by all jobs
+    limiter: ConcurrencyLimiter,
+
+    /// The commands in progress, shared by all jobs
+    active_runs: ActiveRuns,
+
+    /// Runs started outside a schedule: @reboot jobs and runs started
+    /// through the control socket
+    triggered_runs: JoinSet<()>,
+
+    /// Set when the daemon shuts down, so triggered runs that haven't
+    /// started yet don't
+    shutting_down: watch::Sender<bool>,
+
+    /// When the daemon started
+    started_at: DateTime<Utc>,
+
+    /// The most recent job executors that stopped unexpectedly, oldest first
+    executor_restarts: VecDeque<ExecutorRestart>,
+
+    /// Set until the jobs have been loaded once. Only the executors started
+    /// then catch up on fire times missed while the daemon was down.
+    starting: bool,
+}
+
+/// How often the jobs are reloaded from disk when changes can't be watched
+const RELOAD_INTERVAL: Duration = Duration::from_secs(30);
+
+/// How often the jobs are reloaded from disk anyway while changes are watched,
+/// in case a change is missed
+const WATCHED_RELOAD_INTERVAL: Duration = Duration::from_secs(300);
+
+/// How long to wait after a file changes before reloading, so that a burst of
+/// writes leads to a single reload
+const RELOAD_DELAY: Duration = Duration::from_millis(500);
+
+/// How many executor restarts the daemon remembers for `cronr status`
+const MAX_EXECUTOR_RESTARTS: usize = 10;
+
+/// How often to check whether the runs in progress have finished on shutdown
+const SHUTDOWN_POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+/// How often `start` and `stop` check on the daemon process
+const PROCESS_POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+/// How long `start` waits for the daemon to open its control socket
+const START_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// How long `stop` waits for the daemon to exit on top of the time it may
+/// take to stop the runs in progress
+pub const STOP_MARGIN: Duration = Duration::from_secs(5);
+
+/// Wait for the next control request, or forever if there is no control socket
+async fn next_request(
+    requests: Option<&mut mpsc::Receiver<ControlRequest>>,
+) -> Option<ControlRequest> {
+    match requests {
+        Some(requests) => requests.recv().await,
+        None => std::future::pending().await,
+    }
+}
+
+/// Listen for SIGHUP, which asks the daemon to reload its configuration
+#[cfg(unix)]
+fn listen_for_hangup() -> Option<tokio::signal::unix::Signal> {
+    use tokio::signal::unix::{SignalKind, signal};
+
+    match signal(SignalKind::hangup()) {
+        Ok(hangup) => Some(hangup),
+        Err(e) => {
+            log::error!("Failed to set up SIGHUP handler: {}", e);
+            None
+        }
+    }
+}
+
+/// Wait for the next SIGHUP, or forever if it can't be received
+#[cfg(unix)]
+async fn next_hangup(hangup: &mut Option<tokio::signal::unix::Signal>) -> Option<()> {
+    match hangup {
+        Some(hangup) => hangup.recv().await,
+        None => std::future::pending().await,
+    }
+}
+
+/// There is no SIGHUP on this platform
+#[cfg(not(unix))]
+fn listen_for_hangup() -> Option<()> {
+    None
+}
+
+/// Wait forever, as there is no SIGHUP on this platform
+#[cfg(not(unix))]
+async fn next_hangup(_hangup: &mut Option<()>) -> Option<()> {
+    std::future::pending().await
+}
+
+/// The jobs a reload added, removed or changed
+#[derive(Debug, Default, PartialEq)]
+struct ReloadSummary {
+    /// The IDs of the new jobs
+    added: Vec<usize>,
+
+    /// The IDs of the jobs that are gone
+    removed: Vec<usize>,
+
+    /// The IDs of the jobs whose definition changed
+    changed: Vec<usize>,
+}
+
+impl ReloadSummary {
+    /// Compare the jobs before and after a reload, ignoring runtime state
+    fn between(before: &HashMap<usize, Job>, after: &HashMap<usize, Job>) -> Self {
+        let mut summary = ReloadSummary::default();
+        for (id, job) in after {
+            match before.get(id) {
+                None => summary.added.push(*id),
+                Some(old) if !old.same_definition(job) => summary.changed.push(*id),
+                Some(_) => {}
+            }
+        }
+        summary.removed = before
+            .keys()
+            .filter(|id| !after.contains_key(id))
+            .copied()
+            .collect();
+
+        summary.added.sort_unstable();
+        summary.removed.sort_unstable();
+        summary.changed.sort_unstable();
+        summary
+    }
+}
+
+impl fmt::Display for ReloadSummary {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let list = |ids: &[usize]| {
+            ids.iter()
+                .map(|id| id.to_string())
+                .collect::<Vec<_>>()
+                .join(", ")
+        };
+        let parts: Vec<String> = [
+            ("added", &self.added),
+            ("removed", &self.removed),
+            ("changed", &self.changed),
+        ]
+        .into_iter()
+        .filter(|(_, ids)| !ids.is_empty())
+        .map(|(what, ids)| format!("{}: {}", what, list(ids)))
+        .collect();
+
+        if parts.is_empty() {
+            write!(f, "no changes")
+        } else {
+            write!(f, "{}", parts.join("; "))
+        }
+    }
+}
+
+/// Wait for the next change to a watched file, or forever if nothing is watched
+async fn next_change(watcher: Option<&mut FileWatcher>) -> Option<PathBuf> {
+    match watcher {
+        Some(watcher) => watcher.changed().await,
+        None => std::future::pending().await,
+    }
 }
 
 impl DaemonRunner {
     /// Create a new daemon runner
+    #[allow(dead_code)]
     pub async fn new() -> Result<Self> {
         // Create the job manager
         let job_manager = JobManager::new().await?;
@@ -371,15 +618,34 @@ impl DaemonRunner {
             job_manager,
             job_handles: HashMap::new(),
             job_stop_signals: HashMap::new(),
+            job_definitions: HashMap::new(),
+            stopping_jobs: JoinSet::new(),
+            limiter: ConcurrencyLimiter::default(),
+            active_runs: ActiveRuns::default(),
+            triggered_runs: JoinSet::new(),
+            shutting_down: watch::channel(false).0,
+            started_at: Utc::now(),
+            executor_restarts: VecDeque::new(),
+            starting: true,
         })
     }
 
     /// Create a new daemon runner with existing JobManager
+    #[allow(dead_code)]
     pub async fn with_job_manager(job_manager: JobManager) -> Result<Self> {
         Ok(DaemonRunner {
             job_manager,
             job_handles: HashMap::new(),
             job_stop_signals: HashMap::new(),
+            job_definitions: HashMap::new(),
+            stopping_jobs: JoinSet::new(),
+            limiter: ConcurrencyLimiter::default(),
+            active_runs: ActiveRuns::default(),
+            triggered_runs: JoinSet::new(),
+            shutting_down: watch::channel(false).0,
+            started_at: Utc::now(),
+            executor_restarts: VecDeque::new(),
+            starting: true,
         })
     }
 
@@ -394,6 +660,15 @@ impl DaemonRunner {
             job_manager,
             job_handles: HashMap::new(),
             job_stop_signals: HashMap::new(),
+            job_definitions: HashMap::new(),
+            stopping_jobs: JoinSet::new(),
+            limiter: ConcurrencyLimiter::default(),
+            active_runs: ActiveRuns::default(),
+            triggered_runs: JoinSet::new(),
+            shutting_down: watch::channel(false).0,
+            started_at: Utc::now(),
+            executor_restarts: VecDeque::new(),
+            starting: true,
         })
     }
 
@@ -402,85 +677,366 @@ impl DaemonRunner {
         // Log startup
         log::info!("Daemon starting up");
 
-        loop {
-            // Reload job manager from disk to pick up external changes
-            self.job_manager = JobManager::load().await?;
-            // Get all jobs from the freshly loaded state
-            let jobs = self.job_manager.get_all_jobs().await;
-            log::info!("Loaded {} jobs", jobs.len());
-            // Debug each job's schedule details
-            for (id, job) in &jobs {
-                log::debug!(
-                    "Job {} details: command={}, enabled={}, next_run={:?}, last_executed={:?}, env_vars={}",
-                    id,
-                    job.command(),
-                    job.enabled,
-                    job.next_run(),
-                    job.last_executed,
-                    job.env.len()
-                );
+        // Listen for requests from the CLI. Without the socket the CLI falls
+        // back to changing files, which are picked up on the next reload.
+        let socket_path = self.job_manager.config().socket_path();
+        let mut requests = match control::serve(&socket_path) {
+            Ok(requests) => Some(requests),
+            Err(e) => {
+                log::error!("Control socket unavailable: {}", e);
+                None
             }
+        };
 
-            // Determine jobs to stop: removed or disabled
-            let loaded_ids: std::collections::HashSet<usize> = jobs.keys().cloned().collect();
-            let running_ids: Vec<usize> = self.job_handles.keys().cloned().collect();
-            // Stop jobs that are no longer present
-            for id in running_ids {
-                if !loaded_ids.contains(&id) {
-                    log::info!("Stopping removed job {}", id);
-                    self.stop_job(id).await?;
-                }
+        // Watch the jobs and settings files, so changes are picked up promptly.
+        // Without a watcher they are picked up on the next periodic reload.
+        let config = self.job_manager.config().clone();
+        let jobs_file = config.jobs_file();
+        let mut watcher = match FileWatcher::new(
+            config.data_dir(),
+            &[jobs_file.clone(), config.settings_file()],
+        ) {
+            Ok(watcher) => Some(watcher),
+            Err(e) => {
+                log::warn!("Not watching for file changes: {}", e);
+                None
             }
-            // Stop jobs that have been disabled
-            for (id, job) in &jobs {
-                if !job.enabled && self.job_handles.contains_key(id) {
-                    log::info!("Stopping disabled job {}", id);
-                    self.stop_job(*id).await?;
+        };
+        let reload_interval = if watcher.is_some() {
+            WATCHED_RELOAD_INTERVAL
+        } else {
+            RELOAD_INTERVAL
+        };
+
+        // Apply the concurrency limits, then run @reboot jobs once, before
+        // the scheduled jobs start
+        self.reload_settings();
+        self.start_reboot_jobs().await;
+
+        let shutdown = Self::wait_for_signal();
+        tokio::pin!(shutdown);
+        let mut hangup = listen_for_hangup();
+        'daemon: loop {
+            // A broken jobs file doesn't stop the jobs that are already running
+            if let Err(e) = self.reload().await {
+                log::error!("Failed to reload jobs, keeping the current ones: {}", e);
+            }
+
+            // Wait for shutdown, a file change or the next reload interval,
+            // answering control requests in the meantime
+            let next_reload = tokio::time::sleep(reload_interval);
+            tokio::pin!(next_reload);
+            loop {
+                tokio::select! {
+                    _ = &mut shutdown => {
+                        log::info!("Shutdown signal received");
+                        break 'daemon;
+                    }
+                    _ = &mut next_reload => break,
+                    Some(()) = next_hangup(&mut hangup) => {
+                        log::info!("Received SIGHUP, reloading configuration");
+                        break;
+                    }
+                    Some(path) = next_change(watcher.as_mut()) => {
+                        // The executors' own state updates don't need a reload
+                        if path == jobs_file && config.known_jobs().is_current(&jobs_file) {
+                            log::debug!("Ignoring the daemon's own update of {}", path.display());
+                            continue;
+                        }
+                        let soon = Instant::now() + RELOAD_DELAY;
+                        if next_reload.deadline() > soon {
+                            log::info!("{} changed, reloading", path.display());
+                            next_reload.as_mut().reset(soon);
+                        }
+                    }
+                    Some((request, reply)) = next_request(requests.as_mut()) => {
+                        self.handle_request(request, reply).await;
+                    }
                 }
             }
+        }
 
-            // Detect and clean up completed job executor tasks.
-            // If a job's executor task has finished (e.g., due to an unrecoverable error),
-            // remove it from running jobs so it can be restarted on the next cycle.
-            let completed_ids: Vec<usize> = self
-                .job_handles
-                .iter()
-                .filter(|(_, handle)| handle.is_finished())
-                .map(|(id, _)| *id)
-                .collect();
-            for id in completed_ids {
-                log::warn!(
-                    "Job {} executor task completed unexpectedly, will restart",
+        // Stop all jobs on shutdown, including @reboot and triggered runs
+        self.stop_all_jobs().await?;
+        if requests.is_some()
+            && let Err(e) = fs::remove_file(&socket_path)
+        {
+            log::warn!("Could not remove control socket on shutdown: {}", e);
+        }
+        Ok(())
+    }
+
+    /// Reload the jobs and settings from disk, stopping, restarting and
+    /// starting job executors to match. Jobs that didn't change keep running.
+    async fn reload(&mut self) -> Result<ReloadSummary> {
+        // Reload job manager and settings from disk to pick up external changes
+        let previous = self.job_manager.get_all_jobs().await;
+        self.job_manager = self.job_manager.reload().await?;
+        self.reload_settings();
+        // Get all jobs from the freshly loaded state
+        let jobs = self.job_manager.get_all_jobs().await;
+        let summary = ReloadSummary::between(&previous, &jobs);
+        log::info!("Loaded {} jobs ({})", jobs.len(), summary);
+        // Debug each job's schedule details
+        for (id, job) in &jobs {
+            log::debug!(
+                "Job {} details: command={}, enabled={}, next_run={:?}, last_executed={:?}, env_vars={}",
+                id,
+                job.command(),
+                job.enabled,
+                job.next_run(),
+                job.last_executed,
+                job.env.len()
+            );
+        }
+
+        // Determine jobs to stop: removed or disabled
+        let loaded_ids: std::collections::HashSet<usize> = jobs.keys().cloned().collect();
+        let running_ids: Vec<usize> = self.job_handles.keys().cloned().collect();
+        // Stop jobs that are no longer present
+        for id in running_ids {
+            if !loaded_ids.contains(&id) {
+                log::info!("Stopping removed job {}", id);
+                self.stop_job(id)?;
+            }
+        }
+        // Stop jobs that have been disabled
+        for (id, job) in &jobs {
+            if !job.enabled && self.job_handles.contains_key(id) {
+                log::info!("Stopping disabled job {}", id);
+                self.stop_job(*id)?;
+            }
+        }
+
+        // Restart jobs whose definition was edited since they were started
+        for (id, job) in &jobs {
+            let changed = self
+                .job_definitions
+                .get(id)
+                .is_some_and(|running| !running.same_definition(job));
+            if !job.enabled || !changed {
+                continue;
+            }
+            // @reboot jobs are stopped but only start again with the daemon
+            if job.is_reboot() {
+                log::info!(
+                    "Stopping job {} now that it only runs at daemon startup",
                     id
                 );
-                self.job_handles.remove(&id);
-                self.job_stop_signals.remove(&id);
+            } else {
+                log::info!("Restarting changed job {}: {}", id, job.command());
             }
+            self.stop_job(*id)?;
+        }
+
+        // Detect and clean up completed job executor tasks.
+        // If a job's executor task has finished (e.g., due to an unrecoverable error),
+        // remove it from running jobs so it can be restarted on the next cycle.
+        let completed_ids: Vec<usize> = self
+            .job_handles
+            .iter()
+            .filter(|(_, handle)| handle.is_finished())
+            .map(|(id, _)| *id)
+            .collect();
+        for id in completed_ids {
+            let Some(handle) = self.job_handles.remove(&id) else {
+                continue;
+            };
+            let reason = match handle.await {
+                Ok(Ok(())) => "the executor exited".to_string(),
+                Ok(Err(e)) => e.to_string(),
+                Err(e) => e.to_string(),
+            };
+            log::warn!(
+                "Job {} executor task completed unexpectedly ({}), will restart",
+                id,
+                reason
+            );
+            self.job_stop_signals.remove(&id);
+            self.job_definitions.remove(&id);
+
+            // Remember the restart for `cronr status`
+            if self.executor_restarts.len() == MAX_EXECUTOR_RESTARTS {
+                self.executor_restarts.pop_front();
+            }
+            self.executor_restarts.push_back(ExecutorRestart {
+                job_id: id,
+                at: Utc::now(),
+                reason,
+            });
+        }
+
+        // Start any new enabled jobs not yet running (@reboot jobs only run at startup)
+        for (id, job) in &jobs {
+            if job.enabled && !job.is_reboot() && !self.job_handles.contains_key(id) {
+                // Start new job
+                log::info!("{}", &format!("Starting job {}: {}", id, job.command()));
+                self.start_job(*id, job.clone()).await?;
+            }
+        }
 
-            // Start any new enabled jobs not yet running
-            for (id, job) in &jobs {
-                if job.enabled && !self.job_handles.contains_key(id) {
-                    // Start new job
-                    log::info!("{}", &format!("Starting job {}: {}", id, job.command()));
-                    self.start_job(*id, job.clone()).await?;
+        // Forget manually triggered runs and stopped executors that have finished
+        while self.triggered_runs.try_join_next().is_some() {}
+        while self.stopping_jobs.try_join_next().is_some() {}
+
+        // Executors started from now on are edited or restarted ones
+        self.starting = false;
+
+        Ok(summary)
+    }
+
+    /// Carry out a request from the control socket and send back the response
+    async fn handle_request(&mut self, request: Request, reply: oneshot::Sender<Response>) {
+        let result = match request {
+            Request::Reload => self.reload().await.map(|summary| Response::Done {
+                message: format!("Reloaded jobs ({})", summary),
+            }),
+            Request::Status { job } => self
+                .job_statuses(job)
+                .await
+                .map(|jobs| Response::Status { jobs }),
+            Request::Health => self.job_statuses(None).await.map(|jobs| Response::Health {
+                daemon: DaemonStatus {
+                    pid: std::process::id(),
+                    started_at: self.started_at,
+                    executor_restarts: self.executor_restarts.iter().cloned().collect(),
+                },
+                jobs,
+            }),
+            Request::Trigger { job: id } => {
+                let latest = async { self.job_manager.reload().await?.get_job(id).await };
+                match latest.await {
+                    // The run answers the request once it finishes
+                    Ok(job) => {
+                        self.trigger_run(id, job, reply);
+                        return;
+                    }
+                    Err(e) => Err(e),
                 }
             }
+            Request::Kill { job } => {
+                let killed = self.active_runs.kill(job);
+                Ok(Response::Done {
+                    message: if killed == 0 {
+                        format!("Job {} has no runs in progress", job)
+                    } else {
+                        format!("Killed {} run(s) of job {}", killed, job)
+                    },
+                })
+            }
+        };
+        let response = result.unwrap_or_else(|e| Response::Error {
+            message: e.to_string(),
+        });
+        let _ = reply.send(response);
+    }
 
-            // Wait for shutdown or next reload interval
-            tokio::select! {
-                _ = self.wait_for_signal() => {
-                    log::info!("Shutdown signal received");
-                    break;
+    /// Describe the state of one job, or all jobs
+    async fn job_statuses(&self, only: Option<usize>) -> Result<Vec<JobStatus>> {
+        let mut jobs: Vec<(usize, Job)> = match only {
+            Some(id) => vec![(id, self.job_manager.get_job(id).await?)],
+            None => self.job_manager.get_all_jobs().await.into_iter().collect(),
+        };
+        jobs.sort_by_key(|(id, _)| *id);
+
+        let runs = self.active_runs.list();
+        Ok(jobs
+            .into_iter()
+            .map(|(id, job)| JobStatus {
+                id,
+                scheduled: self
+                    .job_handles
+                    .get(&id)
+                    .is_some_and(|handle| !handle.is_finished()),
+                runs: runs
+                    .iter()
+                    .filter(|run| run.job_id == id)
+                    .cloned()
+                    .collect(),
+                next_run: job.next_run(),
+                name: job.name,
+                command: job.command,
+                enabled: job.enabled,
+            })
+            .collect())
+    }
+
+    /// Start a run of a job now, outside its schedule, answering the request
+    /// for it once the run finishes
+    fn trigger_run(&mut self, id: usize, job: Job, reply: oneshot::Sender<Response>) {
+        let config = self.job_config();
+
+        log::info!("Triggering job {}: {}", id, job.command());
+        let mut shutting_down = self.shutting_down.subscribe();
+        self.triggered_runs.spawn(async move {
+            // Triggered runs count against the concurrency limits too, and
+            // don't start once the daemon is shutting down
+            let _permit = tokio::select! {
+                permit = config
+                    .limiter()
+                    .acquire(id, job.concurrency_group.as_deref(), job.priority) => permit,
+                _ = shutting_down.wait_for(|stopping| *stopping) => {
+                    let _ = reply.send(Response::Error {
+                        message: "The daemon is shutting down".to_string(),
+                    });
+                    return;
                 }
-                _ = tokio::time::sleep(Duration::from_secs(30)) => {
-                    // continue to next cycle
+            };
+            let response = match job.execute(&config, id, None, 1, false, None).await {
+                Ok(status) => {
+                    log::info!("Triggered run of job {} exited with {}", id, status);
+                    Response::Finished {
+                        code: status.code(),
+                    }
                 }
-            }
+                Err(e) => {
+                    if !matches!(e, CronrError::RunDetached) {
+                        log::error!("Triggered run of job {} failed: {}", id, e);
+                    }
+                    Response::Error {
+                        message: e.to_string(),
+                    }
+                }
+            };
+            let _ = reply.send(response);
+        });
+    }
+
+    /// Apply the daemon settings, keeping the current ones if they can't be read
+    fn reload_settings(&self) {
+        match self.job_manager.config().settings() {
+            Ok(settings) => self.limiter.configure(&settings),
+            Err(e) => log::error!(
+                "Failed to load daemon settings, keeping the current ones: {}",
+                e
+            ),
         }
+    }
 
-        // Stop all jobs on shutdown
-        self.stop_all_jobs().await?;
-        Ok(())
+    /// Get the configuration jobs run with, sharing the daemon's limits
+    fn job_config(&self) -> Config {
+        self.job_manager
+            .config()
+            .clone()
+            .with_limiter(self.limiter.clone())
+            .with_active_runs(self.active_runs.clone())
+    }
+
+    /// Run every enabled @reboot job once in the background
+    async fn start_reboot_jobs(&mut self) {
+        let config = self.job_config();
+        for (id, job) in self.job_manager.get_all_jobs().await {
+            if !job.enabled || !job.is_reboot() {
+                continue;
+            }
+
+            log::info!("Starting startup job {}: {}", id, job.command());
+            let config = config.clone();
+            self.triggered_runs.spawn(async move {
+                // Errors are already logged by the executor
+                let _ = JobExecutor::new(job).execute_once(id, config).await;
+            });
+        }
     }
 
     /// Start a job
@@ -488,14 +1044,15 @@ impl DaemonRunner {
         // Check if job is already running
         if self.job_handles.contains_key(&id) {
             log::warn!("Job {} is already running, stopping it first", id);
-            self.stop_job(id).await?;
+            self.stop_job(id)?;
         }
 
         // Create a stop signal channel
         let (stop_tx, stop_rx) = watch::channel(false);
 
         // Clone the job manager config
-        let config = self.job_manager.config().clone();
+        let config = self.job_config();
+        let catch_up = self.starting;
 
         // Start the job in a separate task
         let job_clone = job.clone();
@@ -504,18 +1061,25 @@ impl DaemonRunner {
             let executor = JobExecutor::new(job_clone);
 
             // Run the job
-            executor.execute_with_schedule(id, config, stop_rx).await
+            executor
+                .execute_with_schedule(id, config, stop_rx, catch_up)
+                .await
         });
 
-        // Store the handle and stop signal
+        // Store the handle, stop signal and definition
         self.job_handles.insert(id, handle);
         self.job_stop_signals.insert(id, stop_tx);
+        self.job_definitions.insert(id, job);
 
         Ok(())
     }
 
-    /// Stop a job
-    pub async fn stop_job(&mut self, id: usize) -> Result<()> {
+    /// Stop a job's executor. Its runs in progress are not waited for here,
+    /// so the daemon keeps answering requests while they finish.
+    pub fn stop_job(&mut self, id: usize) -> Result<()> {
+        // Forget the definition the job was started with
+        self.job_definitions.remove(&id);
+
         // Get the stop signal
         let stop_tx = match self.job_stop_signals.remove(&id) {
             Some(tx) => tx,
@@ -539,35 +1103,88 @@ impl DaemonRunner {
             CronrError::CommandExecutionFailed(format!("Failed to send stop signal to job {}", id))
         })?;
 
-        // Wait for the job to stop
-        handle.await.map_err(|e| {
-            CronrError::CommandExecutionFailed(format!("Failed to join job task: {}", e))
-        })??;
+        // Let the executor finish its runs in the background
+        self.stopping_jobs.spawn(async move {
+            match handle.await {
+                Ok(Ok(())) => log::info!("Stopped job {}", id),
+                Ok(Err(e)) => log::error!("Job {} stopped with an error: {}", id, e),
+                Err(e) => log::error!("Failed to join job {} task: {}", id, e),
+            }
+        });
 
         Ok(())
     }
 
-    /// Stop all jobs
+    /// Stop all jobs for shutdown. No new runs start, runs of jobs that detach
+    /// on shutdown are left running, and the other runs get the shutdown grace
+    /// period to finish before they are terminated. This includes the runs of
+    /// executors that were already stopping.
     pub async fn stop_all_jobs(&mut self) -> Result<()> {
-        // Get all job IDs
-        let job_ids: Vec<usize> = self.job_handles.keys().cloned().collect();
+        let grace = match self.job_manager.config().settings() {
+            Ok(settings) => settings.shutdown_grace(),
+            Err(e) => {
+                log::error!(
+                    "Failed to load daemon settings, using the default shutdown grace period: {}",
+                    e
+                );
+                DaemonSettings::default().shutdown_grace()
+            }
+        };
 
-        log::info!("Stopping all jobs ({})", job_ids.len());
+        // Stop starting new runs
+        log::info!("Stopping all jobs ({})", self.job_handles.len());
+        for stop_tx in self.job_stop_signals.values() {
+            stop_tx.send_replace(true);
+        }
+        self.shutting_down.send_replace(true);
 
-        // Stop all jobs
-        for id in job_ids {
-            if let Err(e) = self.stop_job(id).await {
-                log::error!("Failed to stop job {}: {}", id, e);
-            } else {
-                log::info!("Stopped job {}", id);
+        // Leave the runs that may outlive the daemon running
+        let detached = self.active_runs.detach_all();
+        if detached > 0 {
+            log::info!("Leaving {} run(s) running", detached);
+        }
+
+        // Give the other runs the grace period to finish, then terminate them
+        if !self.wait_for_runs(grace).await {
+            let killed = self.active_runs.kill_all();
+            log::warn!(
+                "Terminating {} run(s) still in progress after {}s",
+                killed,
+                grace.as_secs()
+            );
+        }
+
+        // Wait for the executors and the other runs to record how they ended
+        self.job_stop_signals.clear();
+        self.job_definitions.clear();
+        for (id, handle) in self.job_handles.drain() {
+            match handle.await {
+                Ok(Ok(())) => log::info!("Stopped job {}", id),
+                Ok(Err(e)) => log::error!("Job {} stopped with an error: {}", id, e),
+                Err(e) => log::error!("Failed to join job {} task: {}", id, e),
             }
         }
+        while self.stopping_jobs.join_next().await.is_some() {}
+        while self.triggered_runs.join_next().await.is_some() {}
 
         Ok(())
     }
 
+    /// Wait up to the given time for the runs in progress to finish. Returns
+    /// false if some are still running.
+    async fn wait_for_runs(&self, limit: Duration) -> bool {
+        let deadline = Instant::now() + limit;
+        while !self.active_runs.list().is_empty() {
+            if Instant::now() >= deadline {
+                return false;
+            }
+            tokio::time::sleep(SHUTDOWN_POLL_INTERVAL).await;
+        }
+        true
+    }
+
     /// Wait for a termination signal
-    async fn wait_for_signal(&self) -> Result<()> {
+    async fn wait_for_signal() -> Result<()> {
         // Set up the signal handler
         #[cfg(unix)]
         {
@@ -620,8 +1237,220 @@ impl DaemonRunner {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::history::RunOutcome;
+    use crate::job::ShutdownPolicy;
     use tempfile::tempdir;
 
+    #[test]
+    fn test_reload_summary() {
+        let job = |command: &str| Job::new(command.to_string(), "0 * * * * *".to_string()).unwrap();
+        let before = HashMap::from([
+            (1, job("echo one")),
+            (2, job("echo two")),
+            (3, job("echo three")),
+        ]);
+
+        // Only runtime state changed
+        let mut after = before.clone();
+        after.get_mut(&1).unwrap().set_as_run();
+        let summary = ReloadSummary::between(&before, &after);
+        assert_eq!(summary, ReloadSummary::default());
+        assert_eq!(summary.to_string(), "no changes");
+
+        // Jobs added, removed and edited
+        after.remove(&2);
+        after.insert(5, job("echo five"));
+        after.insert(4, job("echo four"));
+        after.get_mut(&3).unwrap().command = "echo edited".to_string();
+        let summary = ReloadSummary::between(&before, &after);
+        assert_eq!(summary.added, vec![4, 5]);
+        assert_eq!(summary.removed, vec![2]);
+        assert_eq!(summary.changed, vec![3]);
+        assert_eq!(summary.to_string(), "added: 4, 5; removed: 2; changed: 3");
+    }
+
+    #[tokio::test]
+    async fn test_reload_stops_job_edited_into_reboot() {
+        let temp_dir = tempdir().unwrap();
+        let config = Config::with_data_dir(temp_dir.path()).unwrap();
+        let job_manager = JobManager::with_config(config.clone()).await.unwrap();
+        let id = job_manager
+            .add_job("echo hello".to_string(), "0 0 0 1 1 *".to_string())
+            .await
+            .unwrap();
+
+        // The scheduled job gets an executor
+        let mut runner = DaemonRunner::with_job_manager(job_manager).await.unwrap();
+        runner.reload().await.unwrap();
+        assert!(runner.job_handles.contains_key(&id));
+
+        // Edit the job on disk so it only runs at daemon startup
+        let editor = JobManager::with_config(config).await.unwrap();
+        let mut job = editor.get_job(id).await.unwrap();
+        job.set_schedule("@reboot".to_string()).unwrap();
+        editor.update_job(id, job).await.unwrap();
+
+        // The old executor is stopped and not restarted
+        runner.reload().await.unwrap();
+        assert!(!runner.job_handles.contains_key(&id));
+        assert!(!runner.job_definitions.contains_key(&id));
+    }
+
+    /// Pass a control request to the daemon, returning where its response comes
+    async fn request(runner: &mut DaemonRunner, request: Request) -> oneshot::Receiver<Response> {
+        let (reply, response) = oneshot::channel();
+        runner.handle_request(request, reply).await;
+        response
+    }
+
+    #[tokio::test]
+    async fn test_trigger_and_kill_requests() {
+        let temp_dir = tempdir().unwrap();
+        let config = Config::with_data_dir(temp_dir.path()).unwrap();
+        let job_manager = JobManager::with_config(config).await.unwrap();
+        let add = async |command: &str| {
+            let mut job = Job::new(command.to_string(), "0 0 0 1 1 *".to_string()).unwrap();
+            job.env.insert("SHELL".to_string(), "/bin/sh".to_string());
+            job_manager.insert_job(job).await.unwrap()
+        };
+        let (exits, sleeps) = (add("exit 3").await, add("sleep 30").await);
+        let mut runner = DaemonRunner::with_job_manager(job_manager).await.unwrap();
+
+        // A triggered run is answered with its exit code once it finishes
+        let response = request(&mut runner, Request::Trigger { job: exits }).await;
+        assert_eq!(
+            response.await.unwrap(),
+            Response::Finished { code: Some(3) }
+        );
+
+        // A run in progress can be killed, which ends its trigger request too
+        let triggered = request(&mut runner, Request::Trigger { job: sleeps }).await;
+        while runner.active_runs.list().is_empty() {
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+        let killed = request(&mut runner, Request::Kill { job: sleeps }).await;
+        assert_eq!(
+            killed.await.unwrap(),
+            Response::Done {
+                message: format!("Killed 1 run(s) of job {}", sleeps)
+            }
+        );
+        assert!(matches!(triggered.await.unwrap(), Response::Error { .. }));
+
+        // Killing a job with nothing running says so
+        let killed = request(&mut runner, Request::Kill { job: sleeps }).await;
+        assert_eq!(
+            killed.await.unwrap(),
+            Response::Done {
+                message: format!("Job {} has no runs in progress", sleeps)
+            }
+        );
+
+        // Unknown jobs can't be triggered
+        let response = request(&mut runner, Request::Trigger { job: 99 }).await;
+        assert!(matches!(response.await.unwrap(), Response::Error { .. }));
+    }
+
+    #[tokio::test]
+    async fn test_requests_answered_while_disabling_running_job() {
+        let temp_dir = tempdir().unwrap();
+        let config = Config::with_data_dir(temp_dir.path()).unwrap();
+        let job_manager = JobManager::with_config(config.clone()).await.unwrap();
+        let mut job = Job::new("sleep 30".to_string(), "* * * * * *".to_string()).unwrap();
+        job.env.insert("SHELL".to_string(), "/bin/sh".to_string());
+        let id = job_manager.insert_job(job).await.unwrap();
+
+        // Start the job and wait for its command to run
+        let mut runner = DaemonRunner::with_job_manager(job_manager).await.unwrap();
+        runner.reload().await.unwrap();
+        while runner.active_runs.list().is_empty() {
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+
+        // Disabling the job doesn't wait for the run in progress
+        let editor = JobManager::with_config(config).await.unwrap();
+        let mut job = editor.get_job(id).await.unwrap();
+        job.disable();
+        editor.update_job(id, job).await.unwrap();
+        let reloaded = request(&mut runner, Request::Reload);
+        let reloaded = tokio::time::timeout(Duration::from_secs(5), reloaded)
+            .await
+            .expect("reload waited for the run in progress");
+        assert!(matches!(reloaded.await.unwrap(), Response::Done { .. }));
+        assert!(!runner.job_handles.contains_key(&id));
+
+        // The run can still be seen and killed
+        let status = request(&mut runner, Request::Status { job: Some(id) }).await;
+        let Response::Status { jobs } = status.await.unwrap() else {
+            panic!("expected a status response");
+        };
+        assert_eq!(jobs[0].runs.len(), 1);
+        let killed = request(&mut runner, Request::Kill { job: id }).await;
+        assert_eq!(
+            killed.await.unwrap(),
+            Response::Done {
+                message: format!("Killed 1 run(s) of job {}", id)
+            }
+        );
+
+        // The stopped executor finishes once its run is killed
+        tokio::time::timeout(Duration::from_secs(5), async {
+            while runner.stopping_jobs.join_next().await.is_some() {}
+        })
+        .await
+        .unwrap();
+        assert!(runner.active_runs.list().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_shutdown_applies_to_runs_of_stopped_jobs() {
+        let temp_dir = tempdir().unwrap();
+        let config = Config::with_data_dir(temp_dir.path()).unwrap();
+        fs::write(config.settings_file(), r#"{"shutdown_grace_secs": 0}"#).unwrap();
+        let job_manager = JobManager::with_config(config.clone()).await.unwrap();
+        let add = async |command: &str, on_shutdown| {
+            let mut job = Job::new(command.to_string(), "* * * * * *".to_string()).unwrap();
+            job.env.insert("SHELL".to_string(), "/bin/sh".to_string());
+            job.on_shutdown = on_shutdown;
+            job.kill_grace_secs = Some(1);
+            job_manager.insert_job(job).await.unwrap()
+        };
+        let detached = add("sleep 5", ShutdownPolicy::Detach).await;
+        let stopped = add("sleep 30", ShutdownPolicy::Stop).await;
+
+        // Start both jobs and wait for their commands to run
+        let mut runner = DaemonRunner::with_job_manager(job_manager).await.unwrap();
+        runner.reload().await.unwrap();
+        while runner.active_runs.list().len() < 2 {
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+
+        // Disable both jobs, which stops their executors but not their runs
+        let editor = JobManager::with_config(config.clone()).await.unwrap();
+        for id in [detached, stopped] {
+            let mut job = editor.get_job(id).await.unwrap();
+            job.disable();
+            editor.update_job(id, job).await.unwrap();
+        }
+        runner.reload().await.unwrap();
+
+        // Shutting down still follows each job's shutdown policy
+        tokio::time::timeout(Duration::from_secs(5), runner.stop_all_jobs())
+            .await
+            .expect("shutdown waited for the runs of stopped jobs")
+            .unwrap();
+        let outcome = |id| {
+            let entries = config.history(id).entries().unwrap();
+            entries
+                .iter()
+                .rev()
+                .map(|entry| entry.outcome)
+                .find(|outcome| *outcome != RunOutcome::Skipped)
+        };
+        assert_eq!(outcome(detached), Some(RunOutcome::Detached));
+        assert_eq!(outcome(stopped), Some(RunOutcome::Interrupted));
+    }
+
     #[test]
     fn test_daemon_pid_file() {
         // Create a temporary directory
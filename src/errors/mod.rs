@@ -1,4 +1,4 @@
-use std::path::PathBuf;
+use std::path::Path;
 use thiserror::Error;
 
 /// Result type for the cron job manager
@@ -12,6 +12,7 @@ pub enum CronrError {
     ConfigError(String),
 
     /// Failed to create or access the data directory
+    #[allow(dead_code)]
     #[error("Failed to access data directory: {0}")]
     DataDirError(String),
 
@@ -19,18 +20,49 @@ pub enum CronrError {
     #[error("Invalid cron expression: {0}")]
     InvalidCronExpression(String),
 
+    /// Failed to load a time zone
+    #[error("Invalid time zone: {0}")]
+    InvalidTimeZone(String),
+
     /// Failed to find a cron job with the given ID
     #[error("Invalid job ID: {0}")]
     InvalidJobId(usize),
 
+    /// Failed to find a cron job with the given ID or name
+    #[error("No job with ID or name: {0}")]
+    JobNotFound(String),
+
+    /// A job name is malformed or already taken
+    #[error("Invalid job name: {0}")]
+    InvalidJobName(String),
+
+    /// A command can't be run in the job's execution mode
+    #[error("Invalid command: {0}")]
+    InvalidCommand(String),
+
     /// Failed to start the daemon process
     #[error("Failed to start daemon: {0}")]
     DaemonStartFailed(String),
 
+    /// The daemon process did not stop
+    #[error("Failed to stop daemon: {0}")]
+    DaemonStopFailed(String),
+
+    /// Failed to connect or send a request to the daemon process
+    #[error("Failed to reach daemon: {0}")]
+    DaemonUnreachable(String),
+
     /// Failed to communicate with the daemon process
     #[error("Failed to communicate with daemon: {0}")]
     DaemonCommunicationFailed(String),
 
+    /// A control message came in a different protocol version
+    #[error(
+        "Unsupported protocol version {0}, expected {}",
+        crate::control::PROTOCOL_VERSION
+    )]
+    UnsupportedProtocolVersion(String),
+
     /// Failed to execute a command
     #[error("Command execution failed: {0}")]
     CommandExecutionFailed(String),
@@ -40,25 +72,36 @@ pub enum CronrError {
     InitializationError(String),
 
     /// Failed to rotate logs
+    #[allow(dead_code)]
     #[error("Log rotation failed: {0}")]
     LogRotationError(String),
 
     /// Job execution error
     #[error("Job execution error: {0}")]
     JobExecutionError(String),
+
+    /// A run was terminated on request before it finished
+    #[error("Run was interrupted")]
+    RunInterrupted,
+
+    /// A run was left running when the daemon shut down
+    #[error("Run was left running at daemon shutdown")]
+    RunDetached,
 }
 
 /// Convert a path error to a CronrError
-pub fn path_error_to_config_error(path: &PathBuf, err: std::io::Error) -> CronrError {
+pub fn path_error_to_config_error(path: &Path, err: std::io::Error) -> CronrError {
     CronrError::ConfigError(format!("Error with path {}: {}", path.display(), err))
 }
 
 /// Convert an IO error to a CronrError for command execution
+#[allow(dead_code)]
 pub fn io_error_to_command_error(err: std::io::Error) -> CronrError {
     CronrError::CommandExecutionFailed(format!("IO error: {}", err))
 }
 
 /// Convert an IO error to a CronrError for log rotation
+#[allow(dead_code)]
 pub fn io_error_to_log_rotation_error(err: std::io::Error) -> CronrError {
     CronrError::LogRotationError(format!("IO error: {}", err))
 }
@@ -0,0 +1,348 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs::{self, File, OpenOptions};
+use std::io::{BufRead, BufReader, BufWriter, Write};
+use std::path::PathBuf;
+use std::process::ExitStatus;
+use std::sync::{Arc, LazyLock, Mutex};
+
+use crate::errors::{CronrError, Result, path_error_to_config_error};
+
+/// How a run of a job ended
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RunOutcome {
+    /// The command exited with status 0
+    Succeeded,
+    /// The command exited with a non-zero status or was killed by a signal
+    Failed,
+    /// The command could not be spawned or waited for
+    Error,
+    /// The command ran past its time limit and was terminated
+    TimedOut,
+    /// The command was terminated before it finished, e.g. to start a newer run
+    Interrupted,
+    /// The run was not started because the previous run was still in progress
+    Skipped,
+    /// The command was left running when the daemon shut down, so its result is unknown
+    Detached,
+}
+
+/// One run of a job, as recorded in its history
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HistoryEntry {
+    /// The run ID, increasing for each run of the job
+    pub run_id: u64,
+
+    /// The fire time the run was scheduled for (None for manual runs)
+    pub scheduled_at: Option<DateTime<Utc>>,
+
+    /// The attempt number for the fire time, counting from 1 (retries are higher)
+    #[serde(default = "first_attempt")]
+    pub attempt: u32,
+
+    /// When the run actually started
+    pub started_at: DateTime<Utc>,
+
+    /// When the run ended
+    pub finished_at: DateTime<Utc>,
+
+    /// How long the run took in milliseconds
+    pub duration_ms: u64,
+
+    /// The PID of the command (if it was spawned)
+    pub pid: Option<u32>,
+
+    /// The exit code of the command (if it exited normally)
+    pub exit_code: Option<i32>,
+
+    /// The signal that terminated the command (if any)
+    pub signal: Option<i32>,
+
+    /// How the run ended
+    pub outcome: RunOutcome,
+
+    /// The error that prevented the command from running (if any)
+    pub error: Option<String>,
+}
+
+/// Attempt number for entries recorded before retries were supported
+fn first_attempt() -> u32 {
+    1
+}
+
+impl HistoryEntry {
+    /// Start recording a run at the current time
+    pub fn start(scheduled_at: Option<DateTime<Utc>>) -> Self {
+        let now = Utc::now();
+        HistoryEntry {
+            run_id: 0,
+            scheduled_at,
+            attempt: 1,
+            started_at: now,
+            finished_at: now,
+            duration_ms: 0,
+            pid: None,
+            exit_code: None,
+            signal: None,
+            outcome: RunOutcome::Error,
+            error: None,
+        }
+    }
+
+    /// Record a fire time that was skipped because the previous run was still going
+    pub fn skipped(scheduled_at: Option<DateTime<Utc>>) -> Self {
+        HistoryEntry {
+            outcome: RunOutcome::Skipped,
+            ..Self::start(scheduled_at)
+        }
+    }
+
+    /// Finish recording a run with the result of waiting for its command
+    pub fn finish(&mut self, result: &Result<ExitStatus>) {
+        self.finished_at = Utc::now();
+        self.duration_ms = (self.finished_at - self.started_at)
+            .num_milliseconds()
+            .max(0) as u64;
+
+        match result {
+            Ok(status) => {
+                self.exit_code = status.code();
+                #[cfg(unix)]
+                {
+                    use std::os::unix::process::ExitStatusExt;
+                    self.signal = status.signal();
+                }
+                self.outcome = if status.success() {
+                    RunOutcome::Succeeded
+                } else {
+                    RunOutcome::Failed
+                };
+            }
+            Err(e) => {
+                self.outcome = RunOutcome::Error;
+                self.error = Some(e.to_string());
+            }
+        }
+    }
+
+    /// Describe the result of the run in a few words
+    pub fn result_summary(&self) -> String {
+        if self.outcome == RunOutcome::Skipped {
+            return "skipped due to overlap".to_string();
+        }
+        if self.outcome == RunOutcome::Detached {
+            return "left running at daemon shutdown".to_string();
+        }
+        if let Some(error) = &self.error {
+            return format!("error: {}", error);
+        }
+        let result = match (self.exit_code, self.signal) {
+            (Some(code), _) => format!("exit {}", code),
+            (None, Some(signal)) => format!("signal {}", signal),
+            (None, None) => format!("{:?}", self.outcome).to_lowercase(),
+        };
+        match self.outcome {
+            RunOutcome::TimedOut => format!("timed out ({})", result),
+            RunOutcome::Interrupted => format!("interrupted ({})", result),
+            _ => result,
+        }
+    }
+}
+
+/// The locks serializing appends within this process, one per history file
+static APPEND_LOCKS: LazyLock<Mutex<HashMap<PathBuf, Arc<Mutex<()>>>>> =
+    LazyLock::new(Default::default);
+
+/// The run history of a single job, stored as one JSON entry per line
+pub struct History {
+    /// The path to the history file
+    path: PathBuf,
+
+    /// Maximum number of entries to keep
+    max_entries: usize,
+}
+
+impl History {
+    /// Create a history backed by the given file
+    pub fn new(path: PathBuf, max_entries: usize) -> Self {
+        History { path, max_entries }
+    }
+
+    /// Get all recorded entries, oldest first
+    pub fn entries(&self) -> Result<Vec<HistoryEntry>> {
+        // No history yet
+        if !self.path.exists() {
+            return Ok(Vec::new());
+        }
+
+        let file = File::open(&self.path).map_err(|e| path_error_to_config_error(&self.path, e))?;
+        let mut entries = Vec::new();
+        for line in BufReader::new(file).lines() {
+            let line = line.map_err(|e| path_error_to_config_error(&self.path, e))?;
+            if line.trim().is_empty() {
+                continue;
+            }
+
+            // Skip entries that can't be parsed rather than losing the whole history
+            match serde_json::from_str(&line) {
+                Ok(entry) => entries.push(entry),
+                Err(e) => log::warn!("Skipping bad entry in {}: {}", self.path.display(), e),
+            }
+        }
+
+        Ok(entries)
+    }
+
+    /// Append an entry, assigning it the next run ID and dropping the oldest
+    /// entries beyond the retention limit. Returns the stored entry.
+    pub fn append(&self, mut entry: HistoryEntry) -> Result<HistoryEntry> {
+        // Create the history directory
+        if let Some(dir) = self.path.parent() {
+            fs::create_dir_all(dir).map_err(|e| path_error_to_config_error(dir, e))?;
+        }
+
+        // Hold the job's locks across the read and the write, so concurrent
+        // runs can't take the same run ID or drop each other's entries
+        let append_lock = APPEND_LOCKS
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .entry(self.path.clone())
+            .or_default()
+            .clone();
+        let _guard = append_lock
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        let _file_lock = self.lock_file()?;
+
+        let mut entries = self.entries()?;
+        entry.run_id = entries.last().map_or(1, |last| last.run_id + 1);
+
+        let line = serde_json::to_string(&entry).map_err(|e| {
+            CronrError::ConfigError(format!("Failed to serialize history entry: {}", e))
+        })?;
+
+        if entries.len() < self.max_entries {
+            // Room left, just append the new line
+            let mut file = OpenOptions::new()
+                .create(true)
+                .append(true)
+                .open(&self.path)
+                .map_err(|e| path_error_to_config_error(&self.path, e))?;
+            writeln!(file, "{}", line).map_err(|e| path_error_to_config_error(&self.path, e))?;
+        } else {
+            // Rewrite the file with only the newest entries
+            entries.push(entry.clone());
+            let keep = entries.split_off(entries.len() - self.max_entries);
+            self.rewrite(&keep)?;
+        }
+
+        Ok(entry)
+    }
+
+    /// Take an advisory lock shared with other processes, such as a
+    /// `cronr run --foreground` next to the daemon. A separate lock file is
+    /// used because rewrites replace the history file. Released when the
+    /// returned file is closed.
+    fn lock_file(&self) -> Result<File> {
+        let lock_path = self.path.with_extension("jsonl.lock");
+        let file = OpenOptions::new()
+            .create(true)
+            .truncate(false)
+            .write(true)
+            .open(&lock_path)
+            .map_err(|e| path_error_to_config_error(&lock_path, e))?;
+
+        #[cfg(unix)]
+        {
+            use nix::fcntl::{FlockArg, flock};
+            use std::os::unix::io::AsRawFd;
+
+            flock(file.as_raw_fd(), FlockArg::LockExclusive).map_err(|e| {
+                CronrError::ConfigError(format!("Failed to lock {}: {}", lock_path.display(), e))
+            })?;
+        }
+
+        Ok(file)
+    }
+
+    /// Replace the history file atomically via a temp file + rename
+    fn rewrite(&self, entries: &[HistoryEntry]) -> Result<()> {
+        let temp_file = self.path.with_extension("jsonl.tmp");
+        let file =
+            File::create(&temp_file).map_err(|e| path_error_to_config_error(&temp_file, e))?;
+        let mut writer = BufWriter::new(file);
+        for entry in entries {
+            serde_json::to_writer(&mut writer, entry).map_err(|e| {
+                CronrError::ConfigError(format!("Failed to write history file: {}", e))
+            })?;
+            writeln!(writer).map_err(|e| path_error_to_config_error(&temp_file, e))?;
+        }
+        writer
+            .flush()
+            .map_err(|e| path_error_to_config_error(&temp_file, e))?;
+        fs::rename(&temp_file, &self.path)
+            .map_err(|e| path_error_to_config_error(&self.path, e))?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_append_assigns_run_ids_and_keeps_retention() {
+        // Create a temporary directory
+        let temp_dir = tempdir().unwrap();
+        let history = History::new(temp_dir.path().join("history").join("0.jsonl"), 3);
+
+        // Record five runs
+        for _ in 0..5 {
+            let mut entry = HistoryEntry::start(None);
+            entry.finish(&Err(CronrError::JobExecutionError("boom".into())));
+            history.append(entry).unwrap();
+        }
+
+        // Only the newest three are kept, with run IDs still increasing
+        let entries = history.entries().unwrap();
+        let run_ids: Vec<u64> = entries.iter().map(|e| e.run_id).collect();
+        assert_eq!(run_ids, vec![3, 4, 5]);
+        assert_eq!(entries[0].outcome, RunOutcome::Error);
+        assert!(entries[0].result_summary().contains("boom"));
+    }
+
+    #[test]
+    fn test_concurrent_appends_keep_every_run() {
+        let temp_dir = tempdir().unwrap();
+        let path = temp_dir.path().join("history").join("0.jsonl");
+
+        // Record runs from several threads at once, each with its own handle
+        // the way separate executors do, going past the retention limit
+        let writers: Vec<_> = (0..8)
+            .map(|_| {
+                let history = History::new(path.clone(), 50);
+                std::thread::spawn(move || {
+                    for _ in 0..20 {
+                        history.append(HistoryEntry::start(None)).unwrap();
+                    }
+                })
+            })
+            .collect();
+        for writer in writers {
+            writer.join().unwrap();
+        }
+
+        // No run ID was taken twice and no entry was lost
+        let run_ids: Vec<u64> = History::new(path, 50)
+            .entries()
+            .unwrap()
+            .iter()
+            .map(|e| e.run_id)
+            .collect();
+        assert_eq!(run_ids, (111..=160).collect::<Vec<u64>>());
+    }
+}
@@ -1,21 +1,28 @@
 use chrono::{DateTime, Utc};
-use cron::Schedule;
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{HashMap, VecDeque};
 use std::fmt;
-use std::process::Stdio;
+use std::io::Write;
+use std::path::PathBuf;
+use std::process::{ExitStatus, Stdio};
 use std::time::Duration;
-use tokio::process::Command;
+use tokio::io::{AsyncRead, AsyncReadExt};
+use tokio::process::{Child, Command};
 use tokio::sync::watch;
+use tokio::task::JoinSet;
 use tokio::time;
 
 use crate::config::Config;
 use crate::errors::CronrError;
 use crate::errors::Result;
+use crate::history::{HistoryEntry, RunOutcome};
+use crate::limits::RunPermit;
 use crate::logger::Logger;
+use crate::runs::{ActiveRun, RunControl};
+use crate::schedule::{JobSchedule, JobTimeZone, LEGACY_TIME_ZONE, REBOOT, normalize_expression};
 
 /// A cron job
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Job {
     /// The command to run
     pub command: String,
@@ -36,18 +43,222 @@ pub struct Job {
     /// This ensures jobs run with the user's PATH and other important env vars
     #[serde(default)]
     pub env: HashMap<String, String>,
+
+    /// The time zone the schedule is evaluated in
+    /// Jobs saved before time zones were supported keep running in UTC
+    #[serde(default = "legacy_time_zone")]
+    pub timezone: String,
+
+    /// The directory the command runs in, captured when the job was created
+    /// Jobs saved before this was supported run in the daemon's directory
+    #[serde(default)]
+    pub working_dir: Option<PathBuf>,
+
+    /// A unique name that can be used instead of the ID
+    #[serde(default)]
+    pub name: Option<String>,
+
+    /// What the job is for
+    #[serde(default)]
+    pub description: Option<String>,
+
+    /// Who is responsible for the job
+    #[serde(default)]
+    pub owner: Option<String>,
+
+    /// Free-form tags for selecting groups of jobs, kept sorted
+    #[serde(default)]
+    pub tags: Vec<String>,
+
+    /// How long a run may take in seconds before it is terminated (None for no limit)
+    #[serde(default)]
+    pub timeout_secs: Option<u64>,
+
+    /// How long to wait after SIGTERM before sending SIGKILL, in seconds
+    /// (None for the default grace period)
+    #[serde(default)]
+    pub kill_grace_secs: Option<u64>,
+
+    /// How failed runs are retried (None to wait for the next fire time)
+    #[serde(default)]
+    pub retry: Option<RetryPolicy>,
+
+    /// What happens when a fire time comes while the previous run is still going
+    #[serde(default)]
+    pub overlap: OverlapPolicy,
+
+    /// What happens to fire times missed while the daemon was down
+    #[serde(default)]
+    pub catch_up: CatchUpPolicy,
+
+    /// Maximum number of missed fire times run by the `all` catch-up policy
+    /// (None for the default limit)
+    #[serde(default)]
+    pub catch_up_limit: Option<u32>,
+
+    /// How late in seconds a missed fire time may be and still be caught up
+    /// (None for no limit)
+    #[serde(default)]
+    pub max_lateness_secs: Option<u64>,
+
+    /// The concurrency group whose limit the job's runs count against (if any)
+    #[serde(default)]
+    pub concurrency_group: Option<String>,
+
+    /// Priority of the job's runs when waiting for a free slot (higher goes first)
+    #[serde(default)]
+    pub priority: i32,
+
+    /// How the command is run
+    #[serde(default)]
+    pub exec_mode: ExecMode,
+
+    /// The shell the command runs in (None for the captured $SHELL)
+    #[serde(default)]
+    pub shell: Option<String>,
+
+    /// What happens to the job's runs in progress when the daemon shuts down
+    #[serde(default)]
+    pub on_shutdown: ShutdownPolicy,
+}
+
+/// How a job's command is run
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ExecMode {
+    /// Through a login shell, which sources profile files first
+    #[default]
+    LoginShell,
+    /// Through a shell, without sourcing profile files
+    Shell,
+    /// Directly, split into a program and arguments with shell quoting rules
+    /// but without a shell (no variables, globs, pipes or redirection)
+    Direct,
+}
+
+/// What to do when a job is due while its previous run is still in progress
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum OverlapPolicy {
+    /// Skip the new run, recording it as skipped in the history
+    #[default]
+    Skip,
+    /// Start the new run once the previous one finishes (at most one waits)
+    Queue,
+    /// Start the new run alongside the previous one
+    Concurrent,
+    /// Kill the previous run and start the new one
+    Replace,
+}
+
+/// What happens to a job's runs in progress when the daemon shuts down
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ShutdownPolicy {
+    /// Wait for the run up to the daemon's shutdown grace period, then terminate it
+    #[default]
+    Stop,
+    /// Leave the run going after the daemon exits. Its output is written
+    /// straight to the job's log files.
+    Detach,
+}
+
+/// What to do with fire times missed while the daemon was not running
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CatchUpPolicy {
+    /// Drop all missed fire times and wait for the next one
+    Skip,
+    /// Run once for the most recent missed fire time
+    #[default]
+    Once,
+    /// Run once for every missed fire time, oldest first, up to the catch-up limit
+    All,
+}
+
+/// Default number of missed fire times run by the `all` catch-up policy
+pub const DEFAULT_CATCH_UP_LIMIT: u32 = 10;
+
+/// How a command's run ended, beyond its exit status
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Termination {
+    /// The command exited on its own
+    Exited,
+    /// The command was terminated for running past its time limit
+    TimedOut,
+    /// The command was terminated on request
+    Cancelled,
+    /// The command was left running when the daemon shut down
+    Detached,
+}
+
+/// How a failed run of a job is retried
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RetryPolicy {
+    /// Maximum number of attempts per fire time, including the first one
+    pub max_attempts: u32,
+
+    /// Delay before the first retry in seconds
+    pub initial_delay_secs: u64,
+
+    /// Factor the delay is multiplied by after each retry
+    pub multiplier: f64,
+
+    /// Upper bound for the delay between retries in seconds
+    pub max_delay_secs: u64,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        RetryPolicy {
+            max_attempts: 3,
+            initial_delay_secs: 30,
+            multiplier: 2.0,
+            max_delay_secs: 60 * 60,
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// Get the delay before the next attempt after the given failed attempt
+    /// (counting from 1), or None if no attempts are left
+    pub fn delay_after(&self, attempt: u32) -> Option<Duration> {
+        if attempt >= self.max_attempts {
+            return None;
+        }
+        let delay = self.initial_delay_secs as f64 * self.multiplier.powi(attempt as i32 - 1);
+        Some(Duration::from_secs_f64(
+            delay.min(self.max_delay_secs as f64),
+        ))
+    }
+}
+
+/// Default time between SIGTERM and SIGKILL for a timed out run
+const DEFAULT_KILL_GRACE: Duration = Duration::from_secs(10);
+
+/// Default time zone for jobs that were saved without one
+fn legacy_time_zone() -> String {
+    LEGACY_TIME_ZONE.to_string()
 }
 
 impl Job {
-    /// Create a new job
+    /// Create a new job scheduled in the system's local time zone
     pub fn new(command: String, cron_expression: String) -> Result<Self> {
-        // Parse the cron expression to validate it
-        let schedule = cron_expression
-            .parse::<Schedule>()
-            .map_err(|e| CronrError::InvalidCronExpression(e.to_string()))?;
+        Self::with_time_zone(command, cron_expression, JobTimeZone::local_name())
+    }
+
+    /// Create a new job scheduled in the given time zone
+    pub fn with_time_zone(
+        command: String,
+        cron_expression: String,
+        timezone: String,
+    ) -> Result<Self> {
+        // Normalize and parse the cron expression to validate it
+        let cron_expression = normalize_expression(&cron_expression)?;
+        let schedule = JobSchedule::parse(&cron_expression, &timezone)?;
 
         // Calculate the next run time
-        let next_run = schedule.upcoming(Utc).next();
+        let next_run = schedule.upcoming().next();
 
         // Capture important environment variables from the user's shell
         // This ensures commands like docker, brew, etc. are found when the job runs
@@ -58,6 +269,9 @@ impl Job {
             }
         }
 
+        // Capture the current directory so relative paths work as in the user's shell
+        let working_dir = std::env::current_dir().ok();
+
         Ok(Job {
             command,
             cron_expression,
@@ -65,22 +279,233 @@ impl Job {
             last_executed: None,
             next_run,
             env,
+            timezone,
+            working_dir,
+            name: None,
+            description: None,
+            owner: None,
+            tags: Vec::new(),
+            timeout_secs: None,
+            kill_grace_secs: None,
+            retry: None,
+            overlap: OverlapPolicy::default(),
+            catch_up: CatchUpPolicy::default(),
+            catch_up_limit: None,
+            max_lateness_secs: None,
+            concurrency_group: None,
+            priority: 0,
+            exec_mode: ExecMode::default(),
+            shell: None,
+            on_shutdown: ShutdownPolicy::default(),
         })
     }
 
+    /// Get the job's schedule in its time zone
+    pub fn schedule(&self) -> Result<JobSchedule> {
+        JobSchedule::parse(&self.cron_expression, &self.timezone)
+    }
+
+    /// Check if the job runs once at daemon startup instead of on a schedule
+    pub fn is_reboot(&self) -> bool {
+        self.cron_expression == REBOOT
+    }
+
     /// Get the command
     pub fn command(&self) -> &str {
         &self.command
     }
 
+    /// Get the program and arguments that run the command in the job's execution mode
+    pub fn program(&self) -> Result<(String, Vec<String>)> {
+        // Use the chosen shell, the user's shell (from captured env), or fall back to /bin/sh
+        let shell = || {
+            self.shell
+                .clone()
+                .or_else(|| self.env.get("SHELL").cloned())
+                .unwrap_or_else(|| "/bin/sh".to_string())
+        };
+
+        match self.exec_mode {
+            ExecMode::LoginShell => Ok((
+                shell(),
+                vec!["-l".into(), "-c".into(), self.command.clone()],
+            )),
+            ExecMode::Shell => Ok((shell(), vec!["-c".into(), self.command.clone()])),
+            ExecMode::Direct => {
+                let mut argv = shlex::split(&self.command)
+                    .filter(|argv| !argv.is_empty())
+                    .ok_or_else(|| {
+                        CronrError::InvalidCommand(format!(
+                            "'{}' can't be split into a program and arguments",
+                            self.command
+                        ))
+                    })?;
+                let program = argv.remove(0);
+                Ok((program, argv))
+            }
+        }
+    }
+
+    /// Set or clear the job's name, validating it
+    /// Names can't be purely numeric, so they never shadow a job ID
+    pub fn set_name(&mut self, name: Option<String>) -> Result<()> {
+        if let Some(name) = &name {
+            if name.is_empty() || name.chars().any(char::is_whitespace) {
+                return Err(CronrError::InvalidJobName(format!(
+                    "'{}' must be non-empty and contain no whitespace",
+                    name
+                )));
+            }
+            if name.chars().all(|c| c.is_ascii_digit()) {
+                return Err(CronrError::InvalidJobName(format!(
+                    "'{}' can't be a number, since numbers are job IDs",
+                    name
+                )));
+            }
+        }
+
+        self.name = name;
+        Ok(())
+    }
+
+    /// Add a tag, keeping the tags sorted and unique
+    pub fn add_tag(&mut self, tag: String) {
+        if let Err(pos) = self.tags.binary_search(&tag) {
+            self.tags.insert(pos, tag);
+        }
+    }
+
+    /// Remove a tag if the job has it
+    pub fn remove_tag(&mut self, tag: &str) {
+        self.tags.retain(|t| t != tag);
+    }
+
+    /// Check if the job has a tag
+    pub fn has_tag(&self, tag: &str) -> bool {
+        self.tags.iter().any(|t| t == tag)
+    }
+
+    /// Get the run time limit (if any)
+    pub fn timeout(&self) -> Option<Duration> {
+        self.timeout_secs.map(Duration::from_secs)
+    }
+
+    /// Get the time to wait after SIGTERM before sending SIGKILL
+    pub fn kill_grace(&self) -> Duration {
+        self.kill_grace_secs
+            .map_or(DEFAULT_KILL_GRACE, Duration::from_secs)
+    }
+
+    /// Get the number of missed fire times the `all` catch-up policy runs
+    pub fn catch_up_limit(&self) -> u32 {
+        self.catch_up_limit.unwrap_or(DEFAULT_CATCH_UP_LIMIT)
+    }
+
+    /// Get the missed fire times to catch up on at `now`, oldest first, or None
+    /// if no fire time was missed. The first missed fire time is the persisted
+    /// next run time, or the fire time after the last run if there is none.
+    pub fn catch_up_fire_times(&self, now: DateTime<Utc>) -> Option<Vec<DateTime<Utc>>> {
+        let schedule = self.schedule().ok()?;
+        let first = match self.next_run {
+            Some(next_run) => next_run,
+            None => schedule.after(self.last_executed?).next()?,
+        };
+        if first > now {
+            return None;
+        }
+
+        // Keep only as many of the most recent fire times as the policy allows
+        let keep = match self.catch_up {
+            CatchUpPolicy::Skip => return Some(Vec::new()),
+            CatchUpPolicy::Once => 1,
+            CatchUpPolicy::All => self.catch_up_limit() as usize,
+        };
+
+        // Fire times later than the max lateness are dropped, so start from there
+        let start = self
+            .max_lateness_secs
+            .and_then(|secs| {
+                now.checked_sub_signed(chrono::Duration::try_seconds(secs.try_into().ok()?)?)
+            })
+            .map_or(first, |earliest| earliest.max(first));
+
+        // Walk back from now, so only the fire times kept are visited
+        let mut times: Vec<_> = schedule
+            .before(now)
+            .take_while(|time| *time > start)
+            .chain(std::iter::once(first).filter(|first| *first >= start))
+            .take(keep)
+            .collect();
+        times.reverse();
+        Some(times)
+    }
+
+    /// Change the cron expression, validating it and recalculating the next run time
+    pub fn set_schedule(&mut self, cron_expression: String) -> Result<()> {
+        // Validate the new expression before touching the job
+        let cron_expression = normalize_expression(&cron_expression)?;
+        let schedule = JobSchedule::parse(&cron_expression, &self.timezone)?;
+
+        self.cron_expression = cron_expression;
+        self.next_run = schedule.upcoming().next();
+
+        Ok(())
+    }
+
+    /// Change the time zone, validating it and recalculating the next run time
+    pub fn set_time_zone(&mut self, timezone: String) -> Result<()> {
+        // Validate the new zone before touching the job
+        let schedule = JobSchedule::parse(&self.cron_expression, &timezone)?;
+
+        self.timezone = timezone;
+        self.next_run = schedule.upcoming().next();
+
+        Ok(())
+    }
+
+    /// Get the job's time zone, falling back to UTC if it can't be loaded
+    pub fn time_zone(&self) -> JobTimeZone {
+        JobTimeZone::load(&self.timezone).unwrap_or_else(|e| {
+            log::warn!("{}, showing times in UTC", e);
+            JobTimeZone::load(LEGACY_TIME_ZONE).unwrap()
+        })
+    }
+
+    /// Calculate the next fire time from now
+    fn next_fire_time(&self) -> Option<DateTime<Utc>> {
+        match self.schedule() {
+            Ok(schedule) => schedule.upcoming().next(),
+            Err(e) => {
+                log::error!("Could not calculate next run time: {}", e);
+                None
+            }
+        }
+    }
+
+    /// Check whether two jobs have the same definition, ignoring runtime state
+    /// (last and next run times) that the executor updates on its own
+    pub fn same_definition(&self, other: &Job) -> bool {
+        let mut this = self.clone();
+        let mut other = other.clone();
+        for job in [&mut this, &mut other] {
+            job.last_executed = None;
+            job.next_run = None;
+        }
+        this == other
+    }
+
     /// Set the job as run at the current time
     pub fn set_as_run(&mut self) {
         // Set the last run time to now
         self.last_executed = Some(Utc::now());
 
         // Recalculate the next run time
-        let schedule = self.cron_expression.parse::<Schedule>().unwrap();
-        self.next_run = schedule.upcoming(Utc).next();
+        self.next_run = self.next_fire_time();
+    }
+
+    /// Move on to the next fire time without running the job
+    pub fn skip_fire_time(&mut self) {
+        self.next_run = self.next_fire_time();
     }
 
     /// Get the next run time
@@ -88,6 +513,12 @@ impl Job {
         self.next_run
     }
 
+    /// Get the next `count` fire times of the job's schedule from now
+    pub fn upcoming(&self, count: usize) -> Result<Vec<DateTime<Utc>>> {
+        let schedule = self.schedule()?;
+        Ok(schedule.upcoming().take(count).collect())
+    }
+
     // The following methods are only used in tests
     #[cfg(test)]
     /// Get the cron expression
@@ -101,19 +532,15 @@ impl Job {
         self.enabled
     }
 
-    #[cfg(test)]
     /// Enable the job
     pub fn enable(&mut self) {
         self.enabled = true;
 
-        // Recalculate the next run time
-        if self.next_run.is_none() {
-            let schedule = self.cron_expression.parse::<Schedule>().unwrap();
-            self.next_run = schedule.upcoming(Utc).next();
-        }
+        // Recalculate the next run time so fire times missed while the job
+        // was disabled are not run on resume
+        self.next_run = self.next_fire_time();
     }
 
-    #[cfg(test)]
     /// Disable the job
     pub fn disable(&mut self) {
         self.enabled = false;
@@ -145,39 +572,84 @@ impl Job {
         false
     }
 
-    /// Run the job
-    pub async fn run(&mut self, config: &Config, job_id: usize) -> Result<()> {
+    /// Get the fire time the next run is for (now for @reboot jobs)
+    pub fn scheduled_fire_time(&self) -> Option<DateTime<Utc>> {
+        Some(self.next_run.unwrap_or_else(Utc::now))
+    }
+
+    /// Run the job. The run is terminated if `cancel` becomes true.
+    pub async fn run(
+        &mut self,
+        config: &Config,
+        job_id: usize,
+        cancel: Option<watch::Receiver<bool>>,
+    ) -> Result<()> {
+        // Remember which fire time this run is for
+        let scheduled_at = self.scheduled_fire_time();
+
         // Advance the schedule immediately to prevent tight retry loops on failure.
         // Even if this execution fails, we should wait for the next scheduled time
-        // rather than retrying immediately.
+        // (or the retry policy's delay) rather than retrying immediately.
         self.set_as_run();
 
-        // Get the stdout and stderr paths
-        let stdout_path = config.stdout_log_path(job_id);
-        let stderr_path = config.stderr_log_path(job_id);
+        // Execute the command
+        self.attempt(config, job_id, scheduled_at, 1, cancel).await
+    }
 
-        // Create a logger with log rotation
-        let logger = Logger::new(
-            stdout_path.clone(),
-            stderr_path.clone(),
-            config.log_rotation().clone(),
-        );
+    /// Make one attempt at running the job for a fire time, without touching
+    /// its schedule. Returns an error if the command fails.
+    pub async fn attempt(
+        &self,
+        config: &Config,
+        job_id: usize,
+        scheduled_at: Option<DateTime<Utc>>,
+        attempt: u32,
+        cancel: Option<watch::Receiver<bool>>,
+    ) -> Result<()> {
+        // Execute the command
+        let status = self
+            .execute(config, job_id, scheduled_at, attempt, false, cancel)
+            .await?;
 
-        // Determine the user's shell (from captured env, or fall back to /bin/sh)
-        let shell = self
-            .env
-            .get("SHELL")
-            .map(|s| s.as_str())
-            .unwrap_or("/bin/sh");
+        // Check exit status and return an error for non-zero exits
+        if status.success() {
+            log::info!("Job {} command exited successfully", job_id);
+            Ok(())
+        } else {
+            let exit_info = status
+                .code()
+                .map_or("signal".to_string(), |c| c.to_string());
+            log::warn!(
+                "Job {} command exited with status: {}",
+                job_id,
+                exit_info
+            );
+            Err(CronrError::JobExecutionError(format!(
+                "Command exited with status: {}",
+                exit_info
+            )))
+        }
+    }
 
-        // Run the command through a login shell so that profile files
+    /// Build the process that runs the job's command, with its environment,
+    /// working directory and own process group
+    fn build_command(&self, job_id: usize) -> Result<Command> {
+        // By default the command runs through a login shell so that profile files
         // (~/.bash_profile, ~/.zprofile, /etc/profile, etc.) are sourced.
         // This ensures PATH and other environment variables are properly set up,
         // even though the daemon process itself runs with a minimal environment.
-        log::debug!("Job {} running via login shell: {} -l -c {:?}", job_id, shell, self.command);
-        let mut command = Command::new(shell);
+        let (program, args) = self.program()?;
+        log::debug!(
+            "Job {} running {:?}: {} {:?}",
+            job_id,
+            self.exec_mode,
+            program,
+            args
+        );
+        let mut command = Command::new(&program);
         command
-            .args(["-l", "-c", &self.command])
+            .args(&args)
+            .stdin(Stdio::null())
             .stdout(Stdio::piped())
             .stderr(Stdio::piped());
 
@@ -186,6 +658,11 @@ impl Job {
             command.env(key, value);
         }
 
+        // Run in the job's working directory
+        if let Some(dir) = &self.working_dir {
+            command.current_dir(dir);
+        }
+
         // Create a new process group for the child process to isolate it from
         // signals sent to the daemon's process group. This prevents signals from
         // interrupting child process system calls (e.g., "Interrupted system call").
@@ -201,77 +678,398 @@ impl Job {
             });
         }
 
-        // Spawn the child process
-        let child = match command.spawn() {
-            Ok(child) => child,
-            Err(e) => {
-                return Err(CronrError::JobExecutionError(format!(
-                    "Failed to spawn command: {}",
-                    e
-                )));
+        Ok(command)
+    }
+
+    /// Execute the job's command once without touching its schedule.
+    /// Output is written to the job's log files as it arrives and, in foreground
+    /// mode, is also streamed to the terminal. The run is recorded in the job's
+    /// history with the fire time it was scheduled for (None for manual runs)
+    /// and its attempt number. The command is terminated if `cancel` becomes
+    /// true. Returns the command's exit status.
+    pub async fn execute(
+        &self,
+        config: &Config,
+        job_id: usize,
+        scheduled_at: Option<DateTime<Utc>>,
+        attempt: u32,
+        foreground: bool,
+        cancel: Option<watch::Receiver<bool>>,
+    ) -> Result<ExitStatus> {
+        // Get the stdout and stderr paths
+        let stdout_path = config.stdout_log_path(job_id);
+        let stderr_path = config.stderr_log_path(job_id);
+
+        // Create a logger with log rotation
+        let logger = Logger::new(
+            stdout_path.clone(),
+            stderr_path.clone(),
+            config.log_rotation().clone(),
+        );
+
+        // Runs that may outlive the daemon write their output straight to the
+        // logs, as nobody would be left to read it from a pipe
+        let detachable = self.on_shutdown == ShutdownPolicy::Detach && !foreground;
+
+        // Spawn the child process and wait for it, recording the run either way
+        let mut entry = HistoryEntry::start(scheduled_at);
+        entry.attempt = attempt;
+        let mut termination = Termination::Exited;
+        let spawned = match &self.working_dir {
+            // Check the directory first, since spawning only reports "not found"
+            Some(dir) if !dir.is_dir() => Err(CronrError::JobExecutionError(format!(
+                "Working directory {} does not exist",
+                dir.display()
+            ))),
+            _ => self.build_command(job_id).and_then(|mut command| {
+                if detachable {
+                    let (stdout, stderr) = logger.open_files()?;
+                    command.stdout(stdout).stderr(stderr);
+                }
+                command.spawn().map_err(|e| {
+                    CronrError::JobExecutionError(format!("Failed to spawn command: {}", e))
+                })
+            }),
+        };
+        let result = match spawned {
+            Ok(child) => {
+                entry.pid = child.id();
+
+                // List the run as in progress while it runs, so it can be killed
+                let (_registration, kill) = config.active_runs().register(ActiveRun {
+                    job_id,
+                    pid: entry.pid,
+                    scheduled_at,
+                    attempt,
+                    started_at: entry.started_at,
+                    detachable,
+                });
+                let limits = (self.timeout(), self.kill_grace());
+                wait_for_child(child, &logger, foreground, limits, cancel, kill)
+                    .await
+                    .and_then(|(status, how)| {
+                        termination = how;
+                        status.ok_or(CronrError::RunDetached)
+                    })
+            }
+            Err(e) => Err(e),
+        };
+        entry.finish(&result);
+        match termination {
+            Termination::Exited => {}
+            Termination::TimedOut => entry.outcome = RunOutcome::TimedOut,
+            Termination::Cancelled => entry.outcome = RunOutcome::Interrupted,
+            Termination::Detached => {
+                entry.outcome = RunOutcome::Detached;
+                entry.error = None;
+            }
+        }
+        if let Err(e) = config.history(job_id).append(entry) {
+            log::warn!("Failed to record history for job {}: {}", job_id, e);
+        }
+
+        // A terminated run fails regardless of how the command exited
+        match termination {
+            Termination::Exited => result,
+            Termination::TimedOut => {
+                log::warn!(
+                    "Job {} timed out after {}s",
+                    job_id,
+                    self.timeout_secs.unwrap_or(0)
+                );
+                Err(CronrError::JobExecutionError(format!(
+                    "Command timed out after {}s",
+                    self.timeout_secs.unwrap_or(0)
+                )))
+            }
+            Termination::Cancelled => {
+                log::warn!("Job {} run was interrupted", job_id);
+                Err(CronrError::RunInterrupted)
+            }
+            Termination::Detached => {
+                log::info!("Job {} run was left running at shutdown", job_id);
+                result
+            }
+        }
+    }
+}
+
+/// Wait for a spawned job command to exit, logging its output as it arrives.
+/// If the command runs longer than the timeout, `cancel` becomes true or the
+/// daemon asks to kill it, its process group is terminated, waiting up to the
+/// kill grace period before using SIGKILL. If the daemon asks to detach it,
+/// it is left running without waiting for it. Returns the exit status (None
+/// if detached) and how the command ended.
+async fn wait_for_child(
+    mut child: Child,
+    logger: &Logger,
+    foreground: bool,
+    (timeout, kill_grace): (Option<Duration>, Duration),
+    mut cancel: Option<watch::Receiver<bool>>,
+    mut control: watch::Receiver<RunControl>,
+) -> Result<(Option<ExitStatus>, Termination)> {
+    // Wait for the command, terminating it when the time limit is reached
+    // or the run is cancelled
+    let stdout = child.stdout.take();
+    let stderr = child.stderr.take();
+    let wait = async {
+        let time_limit = async {
+            match timeout {
+                Some(timeout) => time::sleep(timeout).await,
+                None => std::future::pending().await,
             }
         };
+        let requested = async {
+            control
+                .wait_for(|request| *request != RunControl::Continue)
+                .await
+                .map(|request| *request)
+        };
+        let cancelled = async {
+            tokio::select! {
+                true = signalled(cancel.as_mut()) => Termination::Cancelled,
+                Ok(request) = requested => match request {
+                    RunControl::Detach => Termination::Detached,
+                    _ => Termination::Cancelled,
+                },
+                else => std::future::pending().await,
+            }
+        };
+        let termination = tokio::select! {
+            status = child.wait() => {
+                return status.map(|status| (Some(status), Termination::Exited));
+            }
+            _ = time_limit => Termination::TimedOut,
+            termination = cancelled => termination,
+        };
+        if termination == Termination::Detached {
+            return Ok((None, termination));
+        }
+        terminate_child(&mut child, kill_grace)
+            .await
+            .map(|status| (Some(status), termination))
+    };
+
+    // Copy stdout/stderr into the logs while the command runs, regardless of
+    // exit status, so diagnostic output is available for failed and timed out
+    // jobs too
+    let (stdout_result, stderr_result, status) = tokio::join!(
+        copy_output(stdout, foreground.then(std::io::stdout), |data| {
+            logger.write_stdout(data)
+        }),
+        copy_output(stderr, foreground.then(std::io::stderr), |data| {
+            logger.write_stderr(data)
+        }),
+        wait,
+    );
+    stdout_result?;
+    stderr_result?;
+
+    // Report the exit status once the child has completed
+    status.map_err(|e| CronrError::JobExecutionError(format!("Failed to wait for command: {}", e)))
+}
 
-        // Wait for the child to complete asynchronously (non-blocking)
-        let output = match child.wait_with_output().await {
-            Ok(output) => output,
-            Err(e) => {
-                return Err(CronrError::JobExecutionError(format!(
-                    "Failed to wait for command: {}",
-                    e
-                )));
+/// Terminate a command that ran past its time limit: send SIGTERM to its
+/// process group, then SIGKILL to whatever is left after the grace period
+async fn terminate_child(child: &mut Child, kill_grace: Duration) -> std::io::Result<ExitStatus> {
+    #[cfg(unix)]
+    if let Some(pid) = child.id() {
+        use nix::sys::signal::{Signal, killpg};
+        use nix::unistd::Pid;
+
+        // The command leads its own process group (see setpgid in pre_exec)
+        let pgid = Pid::from_raw(pid as i32);
+        if killpg(pgid, Signal::SIGTERM).is_err() {
+            // No process group, so signal the command alone
+            child.start_kill()?;
+        }
+
+        // Wait for the command and the rest of its group to exit
+        let deadline = time::Instant::now() + kill_grace;
+        let mut status = None;
+        loop {
+            if status.is_none() {
+                status = child.try_wait()?;
             }
+            let group_alive = killpg(pgid, None).is_ok();
+            if status.is_some() && !group_alive {
+                break;
+            }
+            if time::Instant::now() >= deadline {
+                log::warn!(
+                    "Process group {} still running after SIGTERM, sending SIGKILL",
+                    pid
+                );
+                let _ = killpg(pgid, Signal::SIGKILL);
+                break;
+            }
+            time::sleep(Duration::from_millis(100)).await;
+        }
+
+        return match status {
+            Some(status) => Ok(status),
+            None => child.wait().await,
         };
+    }
 
-        // Always write stdout/stderr logs regardless of exit status,
-        // so diagnostic output is available for failed jobs too
-        logger.write_stdout(&output.stdout)?;
-        logger.write_stderr(&output.stderr)?;
+    // Without process groups just kill the command
+    child.kill().await?;
+    child.wait().await
+}
 
-        // Check exit status and return an error for non-zero exits
-        if output.status.success() {
-            log::info!("Job {} command exited successfully", job_id);
-            Ok(())
-        } else {
-            let exit_info = output
-                .status
-                .code()
-                .map_or("signal".to_string(), |c| c.to_string());
-            log::warn!(
-                "Job {} command exited with status: {}",
-                job_id,
-                exit_info
-            );
-            Err(CronrError::JobExecutionError(format!(
-                "Command exited with status: {}",
-                exit_info
-            )))
+/// Copy a child's output stream into a log as it arrives, optionally echoing it
+async fn copy_output<R, W, F>(stream: Option<R>, mut echo: Option<W>, mut log: F) -> Result<()>
+where
+    R: AsyncRead + Unpin,
+    W: Write,
+    F: FnMut(&[u8]) -> Result<()>,
+{
+    let Some(mut stream) = stream else {
+        return Ok(());
+    };
+
+    let mut buf = [0u8; 8192];
+    loop {
+        let n = stream.read(&mut buf).await.map_err(|e| {
+            CronrError::JobExecutionError(format!("Failed to read command output: {}", e))
+        })?;
+        if n == 0 {
+            return Ok(());
+        }
+
+        log(&buf[..n])?;
+        if let Some(echo) = echo.as_mut() {
+            // The terminal going away must not fail the run
+            let _ = echo.write_all(&buf[..n]).and_then(|_| echo.flush());
         }
     }
 }
 
 impl fmt::Display for Job {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // Times are shown in the job's own time zone
+        let time_zone = self.time_zone();
+
         // Format the last run time
         let last_run = match self.last_executed {
-            Some(time) => time.format("%Y-%m-%d %H:%M:%S").to_string(),
+            Some(time) => time_zone.format(time),
             None => "Never".to_string(),
         };
 
         // Format the next run time
         let next_run = match self.next_run {
-            Some(time) => time.format("%Y-%m-%d %H:%M:%S").to_string(),
+            Some(time) => time_zone.format(time),
+            None if self.is_reboot() => "At daemon startup".to_string(),
             None => "Never".to_string(),
         };
 
         // Format the job status
         let status = if self.enabled { "Enabled" } else { "Disabled" };
 
+        // Format the captured environment variable names
+        let mut env_keys: Vec<&str> = self.env.keys().map(|key| key.as_str()).collect();
+        env_keys.sort_unstable();
+        let env = if env_keys.is_empty() {
+            "None".to_string()
+        } else {
+            env_keys.join(", ")
+        };
+
+        // Format the optional metadata
+        let metadata = [
+            ("Name", &self.name),
+            ("Description", &self.description),
+            ("Owner", &self.owner),
+        ];
+        for (label, value) in metadata {
+            if let Some(value) = value {
+                writeln!(f, "{}: {}", label, value)?;
+            }
+        }
+
+        // Format the tags
+        if !self.tags.is_empty() {
+            writeln!(f, "Tags: {}", self.tags.join(", "))?;
+        }
+
+        // Format the overlap policy
+        if self.overlap != OverlapPolicy::default() {
+            writeln!(f, "Overlap: {:?}", self.overlap)?;
+        }
+
+        // Format the concurrency group and priority
+        if let Some(group) = &self.concurrency_group {
+            writeln!(f, "Group: {}", group)?;
+        }
+        if self.priority != 0 {
+            writeln!(f, "Priority: {}", self.priority)?;
+        }
+
+        // Format the catch-up policy
+        if self.catch_up != CatchUpPolicy::default()
+            || self.catch_up_limit.is_some()
+            || self.max_lateness_secs.is_some()
+        {
+            let mut catch_up = format!("{:?}", self.catch_up);
+            if self.catch_up == CatchUpPolicy::All {
+                catch_up.push_str(&format!(" (up to {} runs)", self.catch_up_limit()));
+            }
+            if let Some(lateness) = self.max_lateness_secs {
+                catch_up.push_str(&format!(", dropped after {}s late", lateness));
+            }
+            writeln!(f, "Catch Up: {}", catch_up)?;
+        }
+
+        // Format the retry policy
+        if let Some(retry) = &self.retry {
+            writeln!(
+                f,
+                "Retries: up to {} attempts, {}s apart, x{} each time (max {}s)",
+                retry.max_attempts,
+                retry.initial_delay_secs,
+                retry.multiplier,
+                retry.max_delay_secs
+            )?;
+        }
+
+        // Format the time limit
+        if let Some(timeout) = self.timeout_secs {
+            writeln!(
+                f,
+                "Timeout: {}s (SIGKILL {}s after SIGTERM)",
+                timeout,
+                self.kill_grace().as_secs()
+            )?;
+        }
+
+        // Format the execution mode
+        if self.exec_mode != ExecMode::default() || self.shell.is_some() {
+            let mut exec = format!("{:?}", self.exec_mode);
+            if let Some(shell) = self
+                .shell
+                .as_ref()
+                .filter(|_| self.exec_mode != ExecMode::Direct)
+            {
+                exec.push_str(&format!(" ({})", shell));
+            }
+            writeln!(f, "Exec Mode: {}", exec)?;
+        }
+
+        // Format the shutdown policy
+        if self.on_shutdown != ShutdownPolicy::default() {
+            writeln!(f, "On Shutdown: {:?}", self.on_shutdown)?;
+        }
+
+        // Format the working directory
+        if let Some(dir) = &self.working_dir {
+            writeln!(f, "Working Directory: {}", dir.display())?;
+        }
+
         // Format the job
         write!(
             f,
-            "Command: {}\nSchedule: {}\nStatus: {}\nLast Run: {}\nNext Run: {}",
-            self.command, self.cron_expression, status, last_run, next_run
+            "Command: {}\nSchedule: {}\nTime Zone: {}\nStatus: {}\nLast Run: {}\nNext Run: {}\nEnvironment: {}",
+            self.command, self.cron_expression, self.timezone, status, last_run, next_run, env
         )
     }
 }
@@ -288,14 +1086,178 @@ impl JobExecutor {
         JobExecutor { job }
     }
 
-    /// Execute the job according to its schedule
+    /// Execute an @reboot job once, as the daemon starts
+    pub async fn execute_once(&self, id: usize, config: Config) -> Result<()> {
+        let mut job = self.job.clone();
+
+        // Run the job through the normal logging path
+        log::info!("Executing startup job {}: {}", id, job.command());
+        let result = Self::run_with_retries(&mut job, id, &config, None, None)
+            .await
+            .unwrap_or(Ok(()));
+        match &result {
+            Ok(()) => log::info!("Startup job {} executed successfully", id),
+            Err(CronrError::RunDetached) => {}
+            Err(e) => log::error!("Failed to execute startup job {}: {}", id, e),
+        }
+
+        // Persist the last run time
+        if let Err(e) = config.update_job_state(id, &job) {
+            log::error!("Failed to persist job {} state: {}", id, e);
+        }
+
+        result
+    }
+
+    /// Run a job for its current fire time, retrying failed attempts according
+    /// to its retry policy. Retries are never started at or after the job's
+    /// next fire time. Each attempt first waits for a free slot under the
+    /// daemon's concurrency limits. The command is terminated if `cancel`
+    /// becomes true. Returns the result of the last attempt, or None if the job
+    /// was stopped or cancelled while waiting for a slot or to retry.
+    async fn run_with_retries(
+        job: &mut Job,
+        id: usize,
+        config: &Config,
+        mut stop_signal: Option<watch::Receiver<bool>>,
+        mut cancel: Option<watch::Receiver<bool>>,
+    ) -> Option<Result<()>> {
+        // Make the first attempt once there is a free slot, advancing the schedule
+        let scheduled_at = job.scheduled_fire_time();
+        let permit = Self::wait_for_slot(job, id, config, &mut stop_signal, &mut cancel).await?;
+        let mut result = job.run(config, id, cancel.clone()).await;
+        drop(permit);
+        let mut attempt = 1;
+
+        while let Err(e) = &result {
+            // Runs that were deliberately terminated or left running are not retried
+            if matches!(e, CronrError::RunInterrupted | CronrError::RunDetached) {
+                break;
+            }
+
+            // Check if the policy allows another attempt
+            let Some(delay) = job
+                .retry
+                .as_ref()
+                .and_then(|retry| retry.delay_after(attempt))
+            else {
+                break;
+            };
+            log::warn!("Job {} attempt {} failed: {}", id, attempt, e);
+
+            // Give up rather than overlap the next scheduled run
+            let retry_at = Utc::now() + chrono::Duration::from_std(delay).unwrap_or_default();
+            if let Some(next_run) = job.next_run().filter(|next_run| retry_at >= *next_run) {
+                log::warn!(
+                    "Job {} not retried, the retry would run past the next fire time {}",
+                    id,
+                    next_run
+                );
+                break;
+            }
+
+            // Wait for the retry delay or until stopped or cancelled
+            log::info!(
+                "Retrying job {} in {}s (attempt {})",
+                id,
+                delay.as_secs(),
+                attempt + 1
+            );
+            tokio::select! {
+                _ = time::sleep(delay) => {}
+                true = signalled(stop_signal.as_mut()) => {
+                    log::info!("Job {} received stop signal while waiting to retry", id);
+                    return None;
+                }
+                true = signalled(cancel.as_mut()) => {
+                    log::info!("Job {} was cancelled while waiting to retry", id);
+                    return None;
+                }
+            }
+
+            // Make the next attempt for the same fire time, unless waiting for
+            // a slot took it up to the next fire time
+            attempt += 1;
+            let permit =
+                Self::wait_for_slot(job, id, config, &mut stop_signal, &mut cancel).await?;
+            if let Some(next_run) = job.next_run().filter(|next_run| Utc::now() >= *next_run) {
+                log::warn!(
+                    "Job {} not retried, no slot was free before the next fire time {}",
+                    id,
+                    next_run
+                );
+                break;
+            }
+            log::info!(
+                "Executing job {} (attempt {}): {}",
+                id,
+                attempt,
+                job.command()
+            );
+            result = job
+                .attempt(config, id, scheduled_at, attempt, cancel.clone())
+                .await;
+            drop(permit);
+        }
+
+        Some(result)
+    }
+
+    /// Wait for the concurrency limits to allow one more command of the job.
+    /// Returns None if the job was stopped or cancelled while waiting.
+    async fn wait_for_slot(
+        job: &Job,
+        id: usize,
+        config: &Config,
+        stop_signal: &mut Option<watch::Receiver<bool>>,
+        cancel: &mut Option<watch::Receiver<bool>>,
+    ) -> Option<RunPermit> {
+        let limiter = config.limiter();
+        tokio::select! {
+            permit = limiter.acquire(id, job.concurrency_group.as_deref(), job.priority) => {
+                Some(permit)
+            }
+            true = signalled(stop_signal.as_mut()) => {
+                log::info!("Job {} received stop signal while waiting for a free slot", id);
+                None
+            }
+            true = signalled(cancel.as_mut()) => {
+                log::info!("Job {} was cancelled while waiting for a free slot", id);
+                None
+            }
+        }
+    }
+
+    /// Execute the job according to its schedule. Runs happen in the
+    /// background, and fire times that come while a run is still in progress
+    /// are handled according to the job's overlap policy. With `catch_up`,
+    /// fire times missed while the daemon was down are handled by the job's
+    /// catch-up policy. Otherwise an overdue fire time runs once right away.
     pub async fn execute_with_schedule(
         &self,
         id: usize,
         config: Config,
         mut stop_signal: watch::Receiver<bool>,
+        catch_up: bool,
     ) -> Result<()> {
         let mut job = self.job.clone();
+        let mut runs = ExecutorRuns::new(id, config.clone(), stop_signal.clone());
+
+        // Work out which fire times missed while the daemon was down to run,
+        // then move the schedule past them
+        if catch_up && let Some(missed) = job.catch_up_fire_times(Utc::now()) {
+            log::warn!(
+                "Job {} missed fire times while the daemon was down, catching up {} of them ({:?} policy)",
+                id,
+                missed.len(),
+                job.catch_up
+            );
+            runs.catch_up.extend(missed);
+            job.skip_fire_time();
+            if let Err(e) = config.update_job_state(id, &job) {
+                log::error!("Failed to persist job {} state: {}", id, e);
+            }
+        }
 
         // Calculate the initial sleep time until the next run
         let mut next_run_time = match job.next_run() {
@@ -316,74 +1278,256 @@ impl JobExecutor {
 
         log::info!("Job {} scheduled to run at {}", id, next_run_time);
 
+        // Start catching up on missed fire times, one run at a time
+        runs.start_waiting(&mut job);
+
         loop {
             // Calculate the time until the next run
-            let now = Utc::now();
-
-            if next_run_time > now {
-                // Sleep until the next run time or until stopped
-                let sleep_duration = (next_run_time - now)
-                    .to_std()
-                    .unwrap_or_else(|_| Duration::from_secs(1));
-
-                log::debug!(
-                    "Job {} sleeping for {} seconds",
-                    id,
-                    sleep_duration.as_secs()
-                );
+            let sleep_duration = (next_run_time - Utc::now())
+                .to_std()
+                .unwrap_or(Duration::ZERO);
+
+            log::debug!(
+                "Job {} sleeping for {} seconds",
+                id,
+                sleep_duration.as_secs()
+            );
 
-                // Use select to wait for either the timer or the stop signal
-                tokio::select! {
-                    _ = time::sleep(sleep_duration) => {
-                        // Time to execute
-                    }
-                    _ = stop_signal.changed() => {
-                        // Check if we should stop
-                        if *stop_signal.borrow() {
-                            log::info!("Job {} received stop signal", id);
-                            return Ok(());
-                        }
+            // Wait for the next run time, a run to finish or the stop signal
+            tokio::select! {
+                _ = time::sleep(sleep_duration) => {
+                    // Time to execute
+                }
+                _ = runs.join_next(&mut job), if !runs.is_empty() => {
+                    continue;
+                }
+                _ = stop_signal.changed() => {
+                    // Check if we should stop
+                    if *stop_signal.borrow() {
+                        log::info!("Job {} received stop signal", id);
+
+                        // Let runs in progress finish
+                        runs.join_all().await;
+                        return Ok(());
                     }
+                    continue;
                 }
             }
 
             // Check if current time has passed the next run time
-            let now = Utc::now();
-            if now >= next_run_time {
-                // Time to run the job
-                log::info!("Executing job {}: {}", id, job.command());
-
-                // Run the job
-                if let Err(e) = job.run(&config, id).await {
-                    log::error!("Failed to execute job {}: {}", id, e);
-                } else {
-                    log::info!("Job {} executed successfully", id);
-                }
+            if Utc::now() < next_run_time {
+                continue;
+            }
 
-                // Persist the updated job state (next_run, last_executed) to disk
-                // so the daemon reload cycle and any restarts see accurate info
-                if let Err(e) = config.update_job_state(id, &job) {
-                    log::error!("Failed to persist job {} state: {}", id, e);
+            // Start a run or not, and advance the schedule
+            runs.fire(&mut job, next_run_time).await;
+
+            // Update the next run time
+            next_run_time = match job.next_run() {
+                Some(time) => time,
+                None => {
+                    log::error!("Job {} has no next run time after execution", id);
+                    runs.join_all().await;
+                    return Err(CronrError::JobExecutionError(
+                        "Could not calculate next run time".into(),
+                    ));
                 }
+            };
 
-                // Update the next run time
-                next_run_time = match job.next_run() {
-                    Some(time) => time,
-                    None => {
-                        log::error!("Job {} has no next run time after execution", id);
-                        return Err(CronrError::JobExecutionError(
-                            "Could not calculate next run time".into(),
-                        ));
-                    }
-                };
+            log::info!("Job {} next scheduled run: {}", id, next_run_time);
+        }
+    }
+}
+
+/// The runs a job's executor has started, and the fire times waiting for them
+struct ExecutorRuns {
+    /// The ID of the job
+    id: usize,
+
+    /// The configuration the runs use
+    config: Config,
+
+    /// The executor's stop signal, which stops runs waiting to start or retry
+    stop_signal: watch::Receiver<bool>,
+
+    /// The runs in progress
+    runs: JoinSet<()>,
+
+    /// Missed fire times still to be caught up on, oldest first
+    catch_up: VecDeque<DateTime<Utc>>,
+
+    /// The fire time waiting for the runs in progress to finish (queue policy)
+    queued: Option<DateTime<Utc>>,
+
+    /// The signal that terminates the runs in progress (replace policy)
+    cancel: watch::Sender<bool>,
+}
+
+impl ExecutorRuns {
+    /// Create an empty set of runs for a job
+    fn new(id: usize, config: Config, stop_signal: watch::Receiver<bool>) -> Self {
+        ExecutorRuns {
+            id,
+            config,
+            stop_signal,
+            runs: JoinSet::new(),
+            catch_up: VecDeque::new(),
+            queued: None,
+            cancel: watch::channel(false).0,
+        }
+    }
+
+    /// Check if no run is in progress
+    fn is_empty(&self) -> bool {
+        self.runs.is_empty()
+    }
+
+    /// Handle a fire time of the job, starting a run unless the job's overlap
+    /// policy says otherwise, then advance the job's schedule past it
+    async fn fire(&mut self, job: &mut Job, scheduled_at: DateTime<Utc>) {
+        let id = self.id;
+
+        // Decide whether to start a run while the previous one is in progress
+        let start = match job.overlap {
+            _ if self.runs.is_empty() => true,
+            OverlapPolicy::Skip => {
+                self.record_skipped(scheduled_at);
+                false
+            }
+            OverlapPolicy::Queue if self.queued.is_none() => {
+                log::info!("Job {} queued until the run in progress finishes", id);
+                self.queued = Some(scheduled_at);
+                false
+            }
+            OverlapPolicy::Queue => {
+                self.record_skipped(scheduled_at);
+                false
+            }
+            OverlapPolicy::Concurrent => {
+                log::info!("Job {} starting alongside the run in progress", id);
+                true
+            }
+            OverlapPolicy::Replace => {
+                log::warn!(
+                    "Job {} terminating the run in progress to start a new one",
+                    id
+                );
+                self.cancel.send_replace(true);
+                self.join_all().await;
+                self.cancel = watch::channel(false).0;
+                true
+            }
+        };
+
+        // Start the run and advance the schedule
+        if start {
+            self.spawn(job, scheduled_at);
+            job.set_as_run();
+        } else {
+            job.skip_fire_time();
+        }
+
+        // Persist the updated job state (next_run, last_executed) to disk
+        // so the daemon reload cycle and any restarts see accurate info
+        if let Err(e) = self.config.update_job_state(id, job) {
+            log::error!("Failed to persist job {} state: {}", id, e);
+        }
+    }
 
-                log::info!("Job {} next scheduled run: {}", id, next_run_time);
+    /// Wait for a run to finish, then start the next waiting run once nothing
+    /// else is running
+    async fn join_next(&mut self, job: &mut Job) {
+        self.runs.join_next().await;
+        if self.runs.is_empty() {
+            self.start_waiting(job);
+        }
+    }
+
+    /// Start a run for the next waiting fire time, if any. Missed fire times
+    /// go first, since they are older than the queued one.
+    fn start_waiting(&mut self, job: &mut Job) {
+        let scheduled_at = if let Some(scheduled_at) = self.catch_up.pop_front() {
+            log::info!("Catching up on run of job {} for {}", self.id, scheduled_at);
+            scheduled_at
+        } else if let Some(scheduled_at) = self.queued.take() {
+            log::info!(
+                "Starting queued run of job {} for {}",
+                self.id,
+                scheduled_at
+            );
+            scheduled_at
+        } else {
+            return;
+        };
+        self.start_pending(job, scheduled_at);
+    }
+
+    /// Wait for all runs in progress to finish
+    async fn join_all(&mut self) {
+        while self.runs.join_next().await.is_some() {}
+    }
+
+    /// Start a run of the job for a fire time in the background
+    fn spawn(&mut self, job: &Job, scheduled_at: DateTime<Utc>) {
+        // The run works on its own copy of the job, due at the fire time
+        let id = self.id;
+        let mut job = job.clone();
+        job.next_run = Some(scheduled_at);
+        let config = self.config.clone();
+        let stop_signal = self.stop_signal.clone();
+        let cancel = self.cancel.subscribe();
+
+        log::info!("Executing job {}: {}", id, job.command());
+        self.runs.spawn(async move {
+            match JobExecutor::run_with_retries(
+                &mut job,
+                id,
+                &config,
+                Some(stop_signal),
+                Some(cancel),
+            )
+            .await
+            {
+                Some(Ok(())) => log::info!("Job {} executed successfully", id),
+                // Already logged when the run was left running
+                Some(Err(CronrError::RunDetached)) => {}
+                Some(Err(e)) => log::error!("Failed to execute job {}: {}", id, e),
+                None => {}
             }
+        });
+    }
 
-            // Small sleep to prevent CPU spinning if there's a timing issue
-            time::sleep(Duration::from_millis(100)).await;
+    /// Start a run for a fire time that was waiting for earlier runs to finish,
+    /// recording it as the job's last run without advancing the schedule
+    fn start_pending(&mut self, job: &mut Job, scheduled_at: DateTime<Utc>) {
+        self.spawn(job, scheduled_at);
+        job.last_executed = Some(Utc::now());
+        if let Err(e) = self.config.update_job_state(self.id, job) {
+            log::error!("Failed to persist job {} state: {}", self.id, e);
         }
     }
+
+    /// Record a fire time that was skipped because the previous run was still going
+    fn record_skipped(&self, scheduled_at: DateTime<Utc>) {
+        log::warn!(
+            "Job {} run for {} skipped due to overlap",
+            self.id,
+            scheduled_at
+        );
+        let entry = HistoryEntry::skipped(Some(scheduled_at));
+        if let Err(e) = self.config.history(self.id).append(entry) {
+            log::warn!("Failed to record history for job {}: {}", self.id, e);
+        }
+    }
+}
+
+/// Wait until a signal becomes true. Returns false right away if there is no
+/// signal or its sender is gone.
+async fn signalled(signal: Option<&mut watch::Receiver<bool>>) -> bool {
+    match signal {
+        Some(signal) => signal.wait_for(|value| *value).await.is_ok(),
+        None => false,
+    }
 }
 
 #[cfg(test)]
@@ -442,13 +1586,390 @@ mod tests {
         let mut job = Job::new("false".to_string(), "0 * * * * *".to_string()).unwrap();
 
         // Run the job — `false` exits with status 1
-        let result = job.run(&config, 0).await;
+        let result = job.run(&config, 0, None).await;
         assert!(
             result.is_err(),
             "Expected run() to return an error when the command exits with non-zero status"
         );
     }
 
+    #[tokio::test]
+    async fn test_timeout_terminates_process_group() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let config = Config::with_data_dir(temp_dir.path()).unwrap();
+
+        // Create a job whose background child ignores SIGTERM, so the whole
+        // group has to be killed after the grace period
+        let mut job = Job::new(
+            "echo partial; (trap '' TERM; sleep 30) & sleep 30".to_string(),
+            "0 * * * * *".to_string(),
+        )
+        .unwrap();
+        job.env.insert("SHELL".to_string(), "/bin/sh".to_string());
+        job.timeout_secs = Some(1);
+        job.kill_grace_secs = Some(1);
+
+        // The run fails well before the commands would have finished
+        let started = std::time::Instant::now();
+        let result = job.execute(&config, 0, None, 1, false, None).await;
+        assert!(result.is_err());
+        assert!(started.elapsed() < Duration::from_secs(10));
+
+        // The run is recorded as timed out, and the output so far is kept
+        let entries = config.history(0).entries().unwrap();
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].outcome, RunOutcome::TimedOut);
+        assert!(entries[0].result_summary().starts_with("timed out"));
+        let stdout = std::fs::read_to_string(config.stdout_log_path(0)).unwrap();
+        assert!(stdout.contains("partial"));
+    }
+
+    #[test]
+    fn test_retry_delays_back_off_up_to_max() {
+        let retry = RetryPolicy {
+            max_attempts: 5,
+            initial_delay_secs: 10,
+            multiplier: 3.0,
+            max_delay_secs: 60,
+        };
+
+        // Delays grow by the multiplier, are capped, and run out after the last attempt
+        let delays: Vec<Option<u64>> = (1..=5)
+            .map(|attempt| retry.delay_after(attempt).map(|d| d.as_secs()))
+            .collect();
+        assert_eq!(delays, vec![Some(10), Some(30), Some(60), Some(60), None]);
+    }
+
+    #[tokio::test]
+    async fn test_failed_run_is_retried_before_next_fire_time() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let config = Config::with_data_dir(temp_dir.path()).unwrap();
+
+        // A failing job with immediate retries is attempted up to the limit
+        let mut job = Job::new("false".to_string(), "0 0 0 1 1 *".to_string()).unwrap();
+        job.env.insert("SHELL".to_string(), "/bin/sh".to_string());
+        job.retry = Some(RetryPolicy {
+            max_attempts: 3,
+            initial_delay_secs: 0,
+            ..RetryPolicy::default()
+        });
+        let result = JobExecutor::run_with_retries(&mut job, 0, &config, None, None).await;
+        assert!(matches!(result, Some(Err(_))));
+        let attempts: Vec<u32> = config
+            .history(0)
+            .entries()
+            .unwrap()
+            .iter()
+            .map(|entry| entry.attempt)
+            .collect();
+        assert_eq!(attempts, vec![1, 2, 3]);
+
+        // A retry that would start after the next fire time is not made
+        let mut job = Job::new("false".to_string(), "0 * * * * *".to_string()).unwrap();
+        job.env.insert("SHELL".to_string(), "/bin/sh".to_string());
+        job.retry = Some(RetryPolicy {
+            initial_delay_secs: 120,
+            ..RetryPolicy::default()
+        });
+        let result = JobExecutor::run_with_retries(&mut job, 1, &config, None, None).await;
+        assert!(matches!(result, Some(Err(_))));
+        assert_eq!(config.history(1).entries().unwrap().len(), 1);
+
+        // Nor is one that only gets a slot after the next fire time
+        let limiter = crate::limits::ConcurrencyLimiter::default();
+        limiter.configure(&crate::config::DaemonSettings {
+            max_concurrent_runs: Some(1),
+            ..Default::default()
+        });
+        let config = config.with_limiter(limiter.clone());
+        let mut job = Job::new("sleep 0.3; false".to_string(), "* * * * * *".to_string()).unwrap();
+        job.env.insert("SHELL".to_string(), "/bin/sh".to_string());
+        job.retry = Some(RetryPolicy {
+            initial_delay_secs: 0,
+            ..RetryPolicy::default()
+        });
+        let run_config = config.clone();
+        let run = tokio::spawn(async move {
+            JobExecutor::run_with_retries(&mut job, 2, &run_config, None, None).await
+        });
+        time::sleep(Duration::from_millis(100)).await;
+        let blocker = limiter.acquire(3, None, 0).await;
+        time::sleep(Duration::from_millis(1500)).await;
+        drop(blocker);
+        assert!(matches!(run.await.unwrap(), Some(Err(_))));
+        assert_eq!(config.history(2).entries().unwrap().len(), 1);
+    }
+
+    /// Run a job's executor for a while, then stop it and wait for its runs
+    async fn run_executor_for(job: Job, config: &Config, catch_up: bool, duration: Duration) {
+        let (stop_tx, stop_rx) = watch::channel(false);
+        let executor = JobExecutor::new(job);
+        let config = config.clone();
+        let handle = tokio::spawn(async move {
+            executor
+                .execute_with_schedule(0, config, stop_rx, catch_up)
+                .await
+        });
+        time::sleep(duration).await;
+        stop_tx.send(true).unwrap();
+        handle.await.unwrap().unwrap();
+    }
+
+    /// A job running the given shell command, and the runs of its executor
+    fn overlapping_job(
+        command: &str,
+        overlap: OverlapPolicy,
+        config: &Config,
+    ) -> (Job, ExecutorRuns) {
+        let mut job = Job::new(command.to_string(), "0 0 0 1 1 *".to_string()).unwrap();
+        job.env.insert("SHELL".to_string(), "/bin/sh".to_string());
+        job.overlap = overlap;
+        let runs = ExecutorRuns::new(0, config.clone(), watch::channel(false).1);
+        (job, runs)
+    }
+
+    /// A fire time on the given minute
+    fn fire_time(minute: u32) -> DateTime<Utc> {
+        use chrono::TimeZone;
+        Utc.with_ymd_and_hms(2026, 1, 1, 0, minute, 0).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_overlap_skip_records_skipped_runs() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let config = Config::with_data_dir(temp_dir.path()).unwrap();
+        let (mut job, mut runs) = overlapping_job("sleep 1", OverlapPolicy::Skip, &config);
+
+        // The second fire time comes while the first run is still going
+        runs.fire(&mut job, fire_time(0)).await;
+        runs.fire(&mut job, fire_time(1)).await;
+        runs.join_all().await;
+
+        // It is recorded as skipped, not started
+        let entries = config.history(0).entries().unwrap();
+        let outcomes: Vec<_> = entries
+            .iter()
+            .map(|e| (e.scheduled_at, e.outcome))
+            .collect();
+        assert_eq!(
+            outcomes,
+            vec![
+                (Some(fire_time(1)), RunOutcome::Skipped),
+                (Some(fire_time(0)), RunOutcome::Succeeded),
+            ]
+        );
+        assert_eq!(entries[0].result_summary(), "skipped due to overlap");
+    }
+
+    #[tokio::test]
+    async fn test_overlap_queue_waits_behind_catch_up() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let config = Config::with_data_dir(temp_dir.path()).unwrap();
+        let (mut job, mut runs) = overlapping_job("sleep 0.5", OverlapPolicy::Queue, &config);
+
+        // Two missed fire times are being caught up on when two more come
+        runs.catch_up.extend([fire_time(0), fire_time(1)]);
+        runs.start_waiting(&mut job);
+        runs.fire(&mut job, fire_time(2)).await;
+        runs.fire(&mut job, fire_time(3)).await;
+        while !runs.is_empty() {
+            runs.join_next(&mut job).await;
+        }
+
+        // The first one still gets the queue, and runs after the catch-up runs
+        let entries = config.history(0).entries().unwrap();
+        let outcomes: Vec<_> = entries
+            .iter()
+            .map(|e| (e.scheduled_at, e.outcome))
+            .collect();
+        assert_eq!(
+            outcomes,
+            vec![
+                (Some(fire_time(3)), RunOutcome::Skipped),
+                (Some(fire_time(0)), RunOutcome::Succeeded),
+                (Some(fire_time(1)), RunOutcome::Succeeded),
+                (Some(fire_time(2)), RunOutcome::Succeeded),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_overlap_replace_interrupts_previous_run() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let active_runs = crate::runs::ActiveRuns::default();
+        let config = Config::with_data_dir(temp_dir.path())
+            .unwrap()
+            .with_active_runs(active_runs.clone());
+        let (mut job, mut runs) = overlapping_job("sleep 30", OverlapPolicy::Replace, &config);
+
+        // The second fire time comes once the first run's command has started
+        runs.fire(&mut job, fire_time(0)).await;
+        while active_runs.list().is_empty() {
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+        runs.fire(&mut job, fire_time(1)).await;
+
+        // The first run was terminated for the second one, which is still going
+        let entries = config.history(0).entries().unwrap();
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].scheduled_at, Some(fire_time(0)));
+        assert_eq!(entries[0].outcome, RunOutcome::Interrupted);
+        assert!(!runs.is_empty());
+        runs.cancel.send_replace(true);
+        runs.join_all().await;
+    }
+
+    #[tokio::test]
+    async fn test_shutdown_detaches_or_terminates_runs() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let runs = crate::runs::ActiveRuns::default();
+        let config = Config::with_data_dir(temp_dir.path())
+            .unwrap()
+            .with_active_runs(runs.clone());
+
+        // One job may outlive the daemon, the other may not
+        let mut detached = Job::new(
+            "sleep 1; echo finished".to_string(),
+            "0 * * * * *".to_string(),
+        )
+        .unwrap();
+        detached
+            .env
+            .insert("SHELL".to_string(), "/bin/sh".to_string());
+        detached.on_shutdown = ShutdownPolicy::Detach;
+        let mut stopped = Job::new("sleep 30".to_string(), "0 * * * * *".to_string()).unwrap();
+        stopped
+            .env
+            .insert("SHELL".to_string(), "/bin/sh".to_string());
+        stopped.kill_grace_secs = Some(1);
+
+        let (detached_config, stopped_config) = (config.clone(), config.clone());
+        let detached_run = tokio::spawn(async move {
+            detached
+                .execute(&detached_config, 0, None, 1, false, None)
+                .await
+        });
+        let stopped_run = tokio::spawn(async move {
+            stopped
+                .execute(&stopped_config, 1, None, 1, false, None)
+                .await
+        });
+        while runs.list().len() < 2 {
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+
+        // Detaching only lets go of the job that allows it
+        assert_eq!(runs.detach_all(), 1);
+        let result = detached_run.await.unwrap();
+        assert!(
+            matches!(result, Err(CronrError::RunDetached)),
+            "{:?}",
+            result
+        );
+        assert_eq!(runs.kill_all(), 1);
+        assert!(stopped_run.await.unwrap().is_err());
+        assert!(runs.list().is_empty());
+
+        let outcome = |id| config.history(id).entries().unwrap()[0].outcome;
+        assert_eq!(outcome(0), RunOutcome::Detached);
+        assert_eq!(outcome(1), RunOutcome::Interrupted);
+
+        // The detached command keeps writing to the job's log
+        tokio::time::sleep(Duration::from_millis(1500)).await;
+        let stdout = std::fs::read_to_string(config.stdout_log_path(0)).unwrap();
+        assert!(stdout.contains("finished"));
+    }
+
+    #[test]
+    fn test_catch_up_fire_times_follow_policy() {
+        use chrono::TimeZone;
+
+        // A job that fires every minute and last ran 10.5 minutes ago
+        let at = |min, sec| Utc.with_ymd_and_hms(2026, 1, 1, 0, min, sec).unwrap();
+        let now = at(10, 30);
+        let mut job = Job::new("echo test".to_string(), "0 * * * * *".to_string()).unwrap();
+        job.timezone = "UTC".to_string();
+        job.next_run = Some(at(0, 0));
+
+        // Once runs only the most recent missed fire time
+        assert_eq!(job.catch_up_fire_times(now), Some(vec![at(10, 0)]));
+
+        // Skip runs none of them
+        job.catch_up = CatchUpPolicy::Skip;
+        assert_eq!(job.catch_up_fire_times(now), Some(vec![]));
+
+        // All runs the most recent ones up to the limit, oldest first
+        job.catch_up = CatchUpPolicy::All;
+        job.catch_up_limit = Some(3);
+        assert_eq!(
+            job.catch_up_fire_times(now),
+            Some(vec![at(8, 0), at(9, 0), at(10, 0)])
+        );
+
+        // Fire times later than the max lateness are dropped
+        job.catch_up_limit = Some(100);
+        job.max_lateness_secs = Some(150);
+        assert_eq!(
+            job.catch_up_fire_times(now),
+            Some(vec![at(9, 0), at(10, 0)])
+        );
+
+        // Without a next run time, the fire times after the last run are missed
+        job.next_run = None;
+        job.last_executed = Some(at(9, 30));
+        assert_eq!(job.catch_up_fire_times(now), Some(vec![at(10, 0)]));
+
+        // Nothing is missed if the next run is still to come
+        job.next_run = Some(at(11, 0));
+        assert_eq!(job.catch_up_fire_times(now), None);
+
+        // Years of missed fire times don't have to be walked through
+        job.cron_expression = "* * * * * *".to_string();
+        job.next_run = Some(now - chrono::Duration::days(5 * 365));
+        job.catch_up = CatchUpPolicy::Once;
+        assert_eq!(job.catch_up_fire_times(now), Some(vec![now]));
+    }
+
+    #[tokio::test]
+    async fn test_executor_catches_up_on_missed_runs() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let config = Config::with_data_dir(temp_dir.path()).unwrap();
+
+        // An hourly job that was due five hours ago
+        let mut job = Job::new("true".to_string(), "0 0 * * * *".to_string()).unwrap();
+        job.env.insert("SHELL".to_string(), "/bin/sh".to_string());
+        job.next_run = Some(Utc::now() - chrono::Duration::hours(5));
+        job.catch_up = CatchUpPolicy::All;
+        job.catch_up_limit = Some(3);
+        run_executor_for(job.clone(), &config, true, Duration::from_millis(500)).await;
+
+        // The three most recent missed fire times were run, one after another
+        let entries = config.history(0).entries().unwrap();
+        let caught_up: Vec<_> = entries
+            .iter()
+            .filter(|e| e.scheduled_at.is_some_and(|t| t < e.started_at))
+            .collect();
+        assert_eq!(
+            caught_up.len(),
+            3,
+            "expected three catch-up runs in {:?}",
+            entries
+        );
+        assert!(caught_up.iter().all(|e| e.outcome == RunOutcome::Succeeded));
+        assert!(
+            caught_up
+                .windows(2)
+                .all(|w| w[0].scheduled_at < w[1].scheduled_at)
+        );
+
+        // An executor restarted while the daemon runs only makes the overdue run
+        let config = Config::with_data_dir(temp_dir.path().join("restarted")).unwrap();
+        run_executor_for(job.clone(), &config, false, Duration::from_millis(500)).await;
+        let entries = config.history(0).entries().unwrap();
+        assert_eq!(entries.len(), 1, "expected a single run in {:?}", entries);
+        assert_eq!(entries[0].scheduled_at, job.next_run);
+    }
+
     /// Test that a failed job run still advances the schedule.
     /// This prevents execute_with_schedule from spinning in a tight retry loop
     /// when a job's command fails to spawn.
@@ -469,7 +1990,7 @@ mod tests {
         job.next_run = Some(past_time);
 
         // Run the job - should fail because the command doesn't exist
-        let result = job.run(&config, 0).await;
+        let result = job.run(&config, 0, None).await;
         assert!(result.is_err(), "Expected job to fail with non-existent command");
 
         // After the fix: next_run should advance to the future to prevent tight retry loops
@@ -490,7 +2011,7 @@ mod tests {
         // Use a command that only works when interpreted by a shell (echo is a shell builtin)
         let mut job = Job::new("echo hello_from_shell".to_string(), "0 * * * * *".to_string()).unwrap();
 
-        let result = job.run(&config, 0).await;
+        let result = job.run(&config, 0, None).await;
         assert!(result.is_ok(), "Expected shell command to succeed: {:?}", result);
 
         // Verify stdout was captured to the log file
@@ -502,6 +2023,87 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn test_run_uses_working_directory() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let config = Config::with_data_dir(temp_dir.path()).unwrap();
+
+        // A job that reads a file by relative path from its working directory
+        let work_dir = temp_dir.path().join("work");
+        std::fs::create_dir(&work_dir).unwrap();
+        std::fs::write(work_dir.join("input.txt"), "relative_path_works").unwrap();
+        let mut job = Job::new("cat input.txt".to_string(), "0 * * * * *".to_string()).unwrap();
+        job.env.insert("SHELL".to_string(), "/bin/sh".to_string());
+        job.working_dir = Some(work_dir.clone());
+
+        job.run(&config, 0, None).await.unwrap();
+        let stdout_log = std::fs::read_to_string(config.stdout_log_path(0)).unwrap();
+        assert!(
+            stdout_log.contains("relative_path_works"),
+            "got: {}",
+            stdout_log
+        );
+
+        // A missing directory fails the run with a clear error
+        std::fs::remove_file(work_dir.join("input.txt")).unwrap();
+        std::fs::remove_dir(&work_dir).unwrap();
+        let error = job.run(&config, 0, None).await.unwrap_err().to_string();
+        assert!(error.contains("Working directory"), "got: {}", error);
+        let entries = config.history(0).entries().unwrap();
+        assert!(entries[1].result_summary().contains("does not exist"));
+    }
+
+    #[test]
+    fn test_program_follows_exec_mode() {
+        let mut job = Job::new("echo 'a b' $HOME".to_string(), "0 * * * * *".to_string()).unwrap();
+        job.env.insert("SHELL".to_string(), "/bin/bash".to_string());
+        let args = |args: &[&str]| args.iter().map(|arg| arg.to_string()).collect::<Vec<_>>();
+
+        // Login shell by default, through the captured $SHELL
+        let (program, login_args) = job.program().unwrap();
+        assert_eq!(program, "/bin/bash");
+        assert_eq!(login_args, args(&["-l", "-c", "echo 'a b' $HOME"]));
+
+        // A non-login shell, optionally a specific one
+        job.exec_mode = ExecMode::Shell;
+        job.shell = Some("/usr/bin/fish".to_string());
+        let (program, shell_args) = job.program().unwrap();
+        assert_eq!(program, "/usr/bin/fish");
+        assert_eq!(shell_args, args(&["-c", "echo 'a b' $HOME"]));
+
+        // Direct exec splits with quoting rules but expands nothing
+        job.exec_mode = ExecMode::Direct;
+        let (program, direct_args) = job.program().unwrap();
+        assert_eq!(program, "echo");
+        assert_eq!(direct_args, args(&["a b", "$HOME"]));
+
+        // Commands that can't be split are rejected
+        job.command = "echo 'unterminated".to_string();
+        assert!(matches!(job.program(), Err(CronrError::InvalidCommand(_))));
+    }
+
+    #[tokio::test]
+    async fn test_direct_exec_runs_without_shell() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let config = Config::with_data_dir(temp_dir.path()).unwrap();
+
+        // Without a shell, variables are passed through literally
+        let mut job = Job::new(
+            "echo $HOME 'two words'".to_string(),
+            "0 * * * * *".to_string(),
+        )
+        .unwrap();
+        job.exec_mode = ExecMode::Direct;
+        job.run(&config, 0, None).await.unwrap();
+
+        let stdout_log = std::fs::read_to_string(config.stdout_log_path(0)).unwrap();
+        assert!(
+            stdout_log.contains("$HOME two words"),
+            "got: {}",
+            stdout_log
+        );
+    }
+
     /// Test that the SHELL env var is used and a login shell receives captured env overrides.
     #[tokio::test]
     async fn test_run_passes_captured_env_to_shell() {
@@ -512,7 +2114,7 @@ mod tests {
         let mut job = Job::new("echo $CRONR_TEST_VAR".to_string(), "0 * * * * *".to_string()).unwrap();
         job.env.insert("CRONR_TEST_VAR".to_string(), "test_value_42".to_string());
 
-        let result = job.run(&config, 0).await;
+        let result = job.run(&config, 0, None).await;
         assert!(result.is_ok(), "Expected command to succeed: {:?}", result);
 
         // Verify the env var was available inside the command
@@ -523,4 +2125,45 @@ mod tests {
             stdout_log
         );
     }
+
+    #[test]
+    fn test_upcoming_fire_times() {
+        // Create a job that fires at the top of every hour
+        let job = Job::new("echo test".to_string(), "0 0 * * * *".to_string()).unwrap();
+
+        // The fire times are in order, an hour apart, and start at the next run
+        let upcoming = job.upcoming(3).unwrap();
+        assert_eq!(upcoming.len(), 3);
+        assert_eq!(Some(upcoming[0]), job.next_run());
+        assert_eq!(upcoming[1] - upcoming[0], chrono::Duration::hours(1));
+        assert_eq!(upcoming[2] - upcoming[1], chrono::Duration::hours(1));
+    }
+
+    #[test]
+    fn test_set_name() {
+        let mut job = Job::new("echo test".to_string(), "0 * * * * *".to_string()).unwrap();
+
+        // Names that could be confused with IDs or split by the shell are rejected
+        assert!(job.set_name(Some("42".to_string())).is_err());
+        assert!(job.set_name(Some("".to_string())).is_err());
+        assert!(job.set_name(Some("nightly backup".to_string())).is_err());
+
+        // Anything else is accepted and shown
+        job.set_name(Some("nightly-backup".to_string())).unwrap();
+        assert!(job.to_string().contains("Name: nightly-backup"));
+        job.set_name(None).unwrap();
+        assert_eq!(job.name, None);
+    }
+
+    #[test]
+    fn test_reboot_job_has_no_fire_times() {
+        // Create a job that runs at daemon startup
+        let job = Job::new("echo test".to_string(), "@reboot".to_string()).unwrap();
+
+        // It is never scheduled on its own
+        assert!(job.is_reboot());
+        assert_eq!(job.next_run(), None);
+        assert!(job.upcoming(3).unwrap().is_empty());
+        assert!(job.to_string().contains("At daemon startup"));
+    }
 }
@@ -0,0 +1,261 @@
+use std::cmp::Reverse;
+use std::collections::{BTreeMap, HashMap};
+use std::sync::{Arc, Mutex, MutexGuard};
+use tokio::sync::Notify;
+use tokio::time::Instant;
+
+use crate::config::DaemonSettings;
+
+/// Limits how many job commands the daemon runs at the same time, overall and
+/// per concurrency group. Runs that can't start yet wait in a queue ordered by
+/// priority (highest first), then by arrival.
+///
+/// Clones share the same limits and counts.
+#[derive(Debug, Clone, Default)]
+pub struct ConcurrencyLimiter {
+    inner: Arc<Inner>,
+}
+
+#[derive(Debug, Default)]
+struct Inner {
+    /// The limits, running counts and waiting runs
+    state: Mutex<State>,
+
+    /// Wakes waiting runs when a slot may have become free
+    changed: Notify,
+}
+
+#[derive(Debug, Default)]
+struct State {
+    /// Maximum number of commands running at once (None for no limit)
+    max_running: Option<usize>,
+
+    /// Maximum number of commands running at once per group
+    group_limits: HashMap<String, usize>,
+
+    /// Number of commands running
+    running: usize,
+
+    /// Number of commands running per group
+    group_running: HashMap<String, usize>,
+
+    /// The runs waiting for a slot, in the order they get one, with their group
+    waiting: BTreeMap<WaitKey, Option<String>>,
+
+    /// The arrival number of the next waiting run
+    next_arrival: u64,
+}
+
+/// Orders waiting runs by priority (highest first), then by arrival
+type WaitKey = (Reverse<i32>, u64);
+
+impl State {
+    /// Check if a run in the given group could start now
+    fn has_room(&self, group: Option<&str>) -> bool {
+        if self.max_running.is_some_and(|max| self.running >= max) {
+            return false;
+        }
+        match group.and_then(|group| self.group_limits.get(group).map(|max| (group, *max))) {
+            Some((group, max)) => self.group_running.get(group).copied().unwrap_or(0) < max,
+            None => true,
+        }
+    }
+
+    /// Get the waiting run that gets the next free slot. Runs whose group is
+    /// full are passed over, so they don't hold up runs in other groups.
+    fn next_to_start(&self) -> Option<WaitKey> {
+        self.waiting
+            .iter()
+            .find(|(_, group)| self.has_room(group.as_deref()))
+            .map(|(key, _)| *key)
+    }
+}
+
+/// A slot for running one command, given back when dropped
+#[derive(Debug)]
+pub struct RunPermit {
+    limiter: ConcurrencyLimiter,
+    group: Option<String>,
+}
+
+impl Drop for RunPermit {
+    fn drop(&mut self) {
+        let mut state = self.limiter.lock();
+        state.running -= 1;
+        if let Some(group) = &self.group
+            && let Some(count) = state.group_running.get_mut(group)
+        {
+            *count -= 1;
+            if *count == 0 {
+                state.group_running.remove(group);
+            }
+        }
+        drop(state);
+        self.limiter.inner.changed.notify_waiters();
+    }
+}
+
+/// A run's place in the queue, removed if it stops waiting before getting a slot
+struct QueueEntry<'a> {
+    limiter: &'a ConcurrencyLimiter,
+    key: Option<WaitKey>,
+}
+
+impl Drop for QueueEntry<'_> {
+    fn drop(&mut self) {
+        if let Some(key) = self.key {
+            self.limiter.lock().waiting.remove(&key);
+            self.limiter.inner.changed.notify_waiters();
+        }
+    }
+}
+
+impl ConcurrencyLimiter {
+    /// Change the limits. Runs in progress are never stopped, but no new runs
+    /// start until the counts are back under the new limits.
+    pub fn configure(&self, settings: &DaemonSettings) {
+        let mut state = self.lock();
+        state.max_running = settings.max_concurrent_runs;
+        state.group_limits = settings.concurrency_groups.clone();
+        drop(state);
+        self.inner.changed.notify_waiters();
+    }
+
+    /// Wait for a slot to run a command of a job, queueing behind runs with a
+    /// higher priority or that arrived earlier. Dropping the future gives up
+    /// the run's place in the queue.
+    pub async fn acquire(&self, job_id: usize, group: Option<&str>, priority: i32) -> RunPermit {
+        let queued_at = Instant::now();
+        let group = group.map(str::to_string);
+
+        // Join the queue
+        let key = {
+            let mut state = self.lock();
+            let key = (Reverse(priority), state.next_arrival);
+            state.next_arrival += 1;
+            state.waiting.insert(key, group.clone());
+            key
+        };
+        let mut entry = QueueEntry {
+            limiter: self,
+            key: Some(key),
+        };
+
+        let mut logged = false;
+        loop {
+            // Register for wake-ups before checking, so none are missed
+            let changed = self.inner.changed.notified();
+            tokio::pin!(changed);
+            changed.as_mut().enable();
+
+            {
+                let mut state = self.lock();
+                if state.next_to_start() == Some(key) {
+                    // Take the slot and leave the queue
+                    state.waiting.remove(&key);
+                    state.running += 1;
+                    if let Some(group) = &group {
+                        *state.group_running.entry(group.clone()).or_insert(0) += 1;
+                    }
+                    entry.key = None;
+
+                    if logged {
+                        log::info!(
+                            "Job {} waited {:.1}s in the run queue",
+                            job_id,
+                            queued_at.elapsed().as_secs_f64()
+                        );
+                    }
+                    return RunPermit {
+                        limiter: self.clone(),
+                        group,
+                    };
+                }
+
+                if !logged {
+                    log::info!(
+                        "Job {} queued: {} command(s) running, {} waiting{}",
+                        job_id,
+                        state.running,
+                        state.waiting.len(),
+                        group
+                            .as_ref()
+                            .map_or(String::new(), |group| format!(" (group '{}')", group))
+                    );
+                    logged = true;
+                }
+            }
+
+            changed.await;
+        }
+    }
+
+    /// Lock the state, recovering it if a holder panicked
+    fn lock(&self) -> MutexGuard<'_, State> {
+        self.inner
+            .state
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+    use tokio::time::timeout;
+
+    fn limiter(max: Option<usize>, groups: &[(&str, usize)]) -> ConcurrencyLimiter {
+        let limiter = ConcurrencyLimiter::default();
+        limiter.configure(&DaemonSettings {
+            max_concurrent_runs: max,
+            concurrency_groups: groups
+                .iter()
+                .map(|(group, max)| (group.to_string(), *max))
+                .collect(),
+            ..DaemonSettings::default()
+        });
+        limiter
+    }
+
+    #[tokio::test]
+    async fn test_waiting_runs_start_by_priority() {
+        let limiter = limiter(Some(1), &[]);
+        let running = limiter.acquire(0, None, 0).await;
+
+        // Queue a low priority run, then a high priority one
+        let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel();
+        for (job_id, priority) in [(1, 0), (2, 5)] {
+            let limiter = limiter.clone();
+            let tx = tx.clone();
+            tokio::spawn(async move {
+                let _permit = limiter.acquire(job_id, None, priority).await;
+                tx.send(job_id).unwrap();
+                tokio::time::sleep(Duration::from_millis(10)).await;
+            });
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+
+        // Neither starts until the slot is free, then the higher priority goes first
+        assert!(timeout(Duration::from_millis(50), rx.recv()).await.is_err());
+        drop(running);
+        assert_eq!(rx.recv().await, Some(2));
+        assert_eq!(rx.recv().await, Some(1));
+    }
+
+    #[tokio::test]
+    async fn test_group_limit_does_not_block_other_groups() {
+        let limiter = limiter(Some(3), &[("heavy", 1)]);
+        let _heavy = limiter.acquire(0, Some("heavy"), 0).await;
+
+        // A second heavy run waits, but doesn't hold up runs outside the group
+        let waiting = limiter.acquire(1, Some("heavy"), 10);
+        assert!(timeout(Duration::from_millis(50), waiting).await.is_err());
+        let _light = timeout(Duration::from_millis(50), limiter.acquire(2, None, 0))
+            .await
+            .expect("run outside the group should start");
+
+        // Giving up on waiting leaves the queue
+        assert!(limiter.lock().waiting.is_empty());
+    }
+}
@@ -1,5 +1,5 @@
 use std::fs::{self, File, OpenOptions};
-use std::io::{Result as IoResult, Write};
+use std::io::{Read, Result as IoResult, Seek, SeekFrom, Write};
 use std::path::{Path, PathBuf};
 
 use crate::errors::{Result, path_error_to_config_error};
@@ -67,6 +67,43 @@ impl LogRotation {
         self.rotate_log(path)
     }
 
+    /// Get the existing files of a rotated log, oldest first.
+    /// The rotated siblings `.N` to `.1` come before the current log file.
+    pub fn rotated_files<P: AsRef<Path>>(&self, log_path: P) -> Vec<PathBuf> {
+        let path = log_path.as_ref();
+        let path_str = path.to_string_lossy();
+
+        let mut files: Vec<PathBuf> = (1..=self.max_files)
+            .rev()
+            .map(|i| PathBuf::from(format!("{}.{}", path_str, i)))
+            .collect();
+        files.push(path.to_path_buf());
+
+        files.into_iter().filter(|file| file.exists()).collect()
+    }
+
+    /// Read the last `count` lines of a log, reading across rotated files as needed
+    pub fn tail_lines<P: AsRef<Path>>(&self, log_path: P, count: usize) -> Result<Vec<String>> {
+        let mut lines: Vec<String> = Vec::new();
+
+        // Walk from the newest file back to the oldest until enough lines are collected
+        for file in self.rotated_files(log_path).iter().rev() {
+            if lines.len() >= count {
+                break;
+            }
+
+            let data = fs::read(file).map_err(|e| path_error_to_config_error(file, e))?;
+            let text = String::from_utf8_lossy(&data);
+            let mut file_lines: Vec<String> = text.lines().map(str::to_string).collect();
+            file_lines.append(&mut lines);
+            lines = file_lines;
+        }
+
+        // Keep only the last `count` lines
+        let skip = lines.len().saturating_sub(count);
+        Ok(lines.split_off(skip))
+    }
+
     /// Rotate a log file
     fn rotate_log<P: AsRef<Path>>(&self, log_path: P) -> IoResult<()> {
         let path = log_path.as_ref();
@@ -129,25 +166,125 @@ impl Logger {
         self.write_log(&self.stderr_path, data)
     }
 
+    /// Open the stdout and stderr log files for a command to write to directly.
+    /// They are rotated first if needed, but not while the command writes.
+    pub fn open_files(&self) -> Result<(File, File)> {
+        Ok((
+            self.open_log(&self.stdout_path)?,
+            self.open_log(&self.stderr_path)?,
+        ))
+    }
+
     /// Write to a log file with rotation check
     fn write_log(&self, path: &PathBuf, data: &[u8]) -> Result<()> {
+        // Open the log file for appending
+        let mut file = self.open_log(path)?;
+
+        // Write the data
+        file.write_all(data)
+            .map_err(|e| path_error_to_config_error(path, e))?;
+
+        Ok(())
+    }
+
+    /// Open a log file for appending, rotating it first if needed
+    fn open_log(&self, path: &PathBuf) -> Result<File> {
         // Check if the log file needs rotation
         self.rotation
             .check_rotation(path)
             .map_err(|e| path_error_to_config_error(path, e))?;
 
         // Open the log file for appending
-        let mut file = OpenOptions::new()
+        OpenOptions::new()
             .create(true)
             .append(true)
             .open(path)
-            .map_err(|e| path_error_to_config_error(path, e))?;
+            .map_err(|e| path_error_to_config_error(path, e))
+    }
+}
 
-        // Write the data
-        file.write_all(data)
-            .map_err(|e| path_error_to_config_error(path, e))?;
+/// Follows a log file as it grows, continuing across rotations
+pub struct LogFollower {
+    /// The path to the log file
+    path: PathBuf,
+    /// The currently open log file and the position read up to
+    file: Option<(File, u64)>,
+}
 
-        Ok(())
+impl LogFollower {
+    /// Start following a log file from its current end
+    pub fn new(path: PathBuf) -> Self {
+        let file = File::open(&path).ok().and_then(|mut file| {
+            let end = file.seek(SeekFrom::End(0)).ok()?;
+            Some((file, end))
+        });
+        LogFollower { path, file }
+    }
+
+    /// Get the path to the log file
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Read everything written since the last poll.
+    /// When the log has been rotated, the rest of the old file is returned
+    /// first and following continues from the start of the new file.
+    pub fn poll(&mut self) -> Result<Vec<u8>> {
+        let mut data = Vec::new();
+
+        // Drain whatever was appended to the file we have open. The open handle
+        // keeps pointing at the same file even after it is renamed to `.1`.
+        if let Some((file, pos)) = self.file.as_mut() {
+            let read = file
+                .read_to_end(&mut data)
+                .map_err(|e| path_error_to_config_error(&self.path, e))?;
+            *pos += read as u64;
+        }
+
+        // Switch to the new file once the log has been rotated or recreated
+        if self.is_rotated() {
+            self.file = File::open(&self.path).ok().map(|file| (file, 0));
+            if let Some((file, pos)) = self.file.as_mut() {
+                let read = file
+                    .read_to_end(&mut data)
+                    .map_err(|e| path_error_to_config_error(&self.path, e))?;
+                *pos = read as u64;
+            }
+        }
+
+        Ok(data)
+    }
+
+    /// Check whether the path no longer refers to the file we have open
+    fn is_rotated(&self) -> bool {
+        let Ok(current) = fs::metadata(&self.path) else {
+            return false;
+        };
+        let Some((file, pos)) = self.file.as_ref() else {
+            return true;
+        };
+
+        // A log that shrank below our position was truncated or recreated
+        if current.len() < *pos {
+            return true;
+        }
+
+        // A different file at the same path means the log was rotated
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::MetadataExt;
+
+            match file.metadata() {
+                Ok(open) => open.ino() != current.ino() || open.dev() != current.dev(),
+                Err(_) => true,
+            }
+        }
+
+        #[cfg(not(unix))]
+        {
+            let _ = file;
+            false
+        }
     }
 }
 
@@ -187,4 +324,45 @@ mod tests {
         let metadata = fs::metadata(&log_path).unwrap();
         assert_eq!(metadata.len(), 0);
     }
+
+    #[test]
+    fn test_tail_lines_across_rotated_files() {
+        // Create a temporary directory
+        let temp_dir = tempdir().unwrap();
+        let log_path = temp_dir.path().join("test.log");
+        let rotation = LogRotation::with_max_files(100, 3);
+
+        // Two rotated files and the current one, oldest first
+        fs::write(temp_dir.path().join("test.log.2"), "one\ntwo\n").unwrap();
+        fs::write(temp_dir.path().join("test.log.1"), "three\nfour\n").unwrap();
+        fs::write(&log_path, "five\n").unwrap();
+
+        // Lines come back in order, reaching into older files as needed
+        assert_eq!(
+            rotation.tail_lines(&log_path, 4).unwrap(),
+            vec!["two", "three", "four", "five"]
+        );
+        assert_eq!(rotation.tail_lines(&log_path, 1).unwrap(), vec!["five"]);
+        assert_eq!(rotation.tail_lines(&log_path, 10).unwrap().len(), 5);
+    }
+
+    #[test]
+    fn test_log_follower_continues_after_rotation() {
+        // Create a temporary directory
+        let temp_dir = tempdir().unwrap();
+        let log_path = temp_dir.path().join("test.log");
+        let rotation = LogRotation::with_max_files(10, 3);
+        let logger = Logger::new(log_path.clone(), temp_dir.path().join("err.log"), rotation);
+
+        // Existing content is not replayed
+        logger.write_stdout(b"old\n").unwrap();
+        let mut follower = LogFollower::new(log_path.clone());
+        assert!(follower.poll().unwrap().is_empty());
+
+        // New content is picked up, including the tail written just before a rotation
+        logger.write_stdout(b"before rotation\n").unwrap();
+        logger.write_stdout(b"after\n").unwrap();
+        assert!(temp_dir.path().join("test.log.1").exists());
+        assert_eq!(follower.poll().unwrap(), b"before rotation\nafter\n");
+    }
 }
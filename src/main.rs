@@ -3,10 +3,16 @@ use std::process;
 
 mod commands;
 mod config;
+mod control;
 mod daemon;
 mod errors;
+mod history;
 mod job;
+mod limits;
 mod logger;
+mod runs;
+mod schedule;
+mod watcher;
 
 use commands::{Cli, run};
 
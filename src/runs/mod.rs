@@ -0,0 +1,180 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::sync::{Arc, Mutex, MutexGuard};
+use tokio::sync::watch;
+
+/// A job command that is running
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ActiveRun {
+    /// The job the command belongs to
+    pub job_id: usize,
+
+    /// The PID of the command (if known)
+    pub pid: Option<u32>,
+
+    /// The fire time the run is for (None for manual runs)
+    pub scheduled_at: Option<DateTime<Utc>>,
+
+    /// The attempt number for the fire time
+    pub attempt: u32,
+
+    /// When the command was started
+    pub started_at: DateTime<Utc>,
+
+    /// Whether the command may be left running when the daemon shuts down
+    #[serde(default)]
+    pub detachable: bool,
+}
+
+/// What the daemon asks of a run in progress
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RunControl {
+    /// Let the command run
+    Continue,
+    /// Terminate the command
+    Kill,
+    /// Stop waiting for the command and leave it running
+    Detach,
+}
+
+/// The job commands in progress, so they can be listed and killed from
+/// outside the executors that started them.
+///
+/// Clones share the same runs.
+#[derive(Debug, Clone, Default)]
+pub struct ActiveRuns {
+    inner: Arc<Mutex<Registry>>,
+}
+
+#[derive(Debug, Default)]
+struct Registry {
+    /// The key of the next registered run
+    next_key: u64,
+
+    /// The runs in progress, oldest first, with the channel that controls them
+    runs: BTreeMap<u64, (ActiveRun, watch::Sender<RunControl>)>,
+}
+
+/// A run's entry in the registry, removed when dropped
+#[derive(Debug)]
+pub struct Registration {
+    runs: ActiveRuns,
+    key: u64,
+}
+
+impl Drop for Registration {
+    fn drop(&mut self) {
+        self.runs.lock().runs.remove(&self.key);
+    }
+}
+
+impl ActiveRuns {
+    /// Record a run as in progress until the returned registration is dropped.
+    /// The receiver tells the run when it should be killed or detached.
+    pub fn register(&self, run: ActiveRun) -> (Registration, watch::Receiver<RunControl>) {
+        let (control_tx, control_rx) = watch::channel(RunControl::Continue);
+        let mut registry = self.lock();
+        let key = registry.next_key;
+        registry.next_key += 1;
+        registry.runs.insert(key, (run, control_tx));
+
+        let registration = Registration {
+            runs: self.clone(),
+            key,
+        };
+        (registration, control_rx)
+    }
+
+    /// Get the runs in progress, oldest first
+    pub fn list(&self) -> Vec<ActiveRun> {
+        self.lock()
+            .runs
+            .values()
+            .map(|(run, _)| run.clone())
+            .collect()
+    }
+
+    /// Ask every run of a job in progress to stop. Returns how many there were.
+    pub fn kill(&self, job_id: usize) -> usize {
+        self.send(|run| run.job_id == job_id, RunControl::Kill)
+    }
+
+    /// Ask every run in progress to stop. Returns how many there were.
+    pub fn kill_all(&self) -> usize {
+        self.send(|_| true, RunControl::Kill)
+    }
+
+    /// Leave every run in progress that may outlive the daemon running, no
+    /// longer waiting for it. Returns how many there were.
+    pub fn detach_all(&self) -> usize {
+        self.send(|run| run.detachable, RunControl::Detach)
+    }
+
+    /// Send a request to the runs in progress that match. Runs that were
+    /// detached are left alone, even if they haven't let go yet.
+    fn send(&self, matches: impl Fn(&ActiveRun) -> bool, request: RunControl) -> usize {
+        let registry = self.lock();
+        let mut sent = 0;
+        for (run, control) in registry.runs.values() {
+            if matches(run) && *control.borrow() != RunControl::Detach {
+                control.send_replace(request);
+                sent += 1;
+            }
+        }
+        sent
+    }
+
+    /// Lock the registry, recovering it if a holder panicked
+    fn lock(&self) -> MutexGuard<'_, Registry> {
+        self.inner
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn run(job_id: usize) -> ActiveRun {
+        ActiveRun {
+            job_id,
+            pid: Some(100 + job_id as u32),
+            scheduled_at: None,
+            attempt: 1,
+            started_at: Utc::now(),
+            detachable: job_id == 2,
+        }
+    }
+
+    #[test]
+    fn test_register_list_and_kill() {
+        let runs = ActiveRuns::default();
+        let (first, first_kill) = runs.register(run(1));
+        let (_second, second_kill) = runs.register(run(2));
+        let job_ids = |runs: &ActiveRuns| -> Vec<usize> {
+            runs.list().iter().map(|run| run.job_id).collect()
+        };
+        assert_eq!(job_ids(&runs), vec![1, 2]);
+
+        // Killing a job only signals its own runs
+        assert_eq!(runs.kill(1), 1);
+        assert_eq!(*first_kill.borrow(), RunControl::Kill);
+        assert_eq!(*second_kill.borrow(), RunControl::Continue);
+        assert_eq!(runs.kill(3), 0);
+
+        // Only runs that may outlive the daemon are detached
+        assert_eq!(runs.detach_all(), 1);
+        assert_eq!(*first_kill.borrow(), RunControl::Kill);
+        assert_eq!(*second_kill.borrow(), RunControl::Detach);
+
+        // Detached runs are not killed afterwards
+        assert_eq!(runs.kill_all(), 1);
+        assert_eq!(*second_kill.borrow(), RunControl::Detach);
+
+        // Finished runs are no longer listed
+        drop(first);
+        assert_eq!(job_ids(&runs), vec![2]);
+    }
+}
@@ -0,0 +1,582 @@
+use chrono::{DateTime, Duration, FixedOffset, NaiveDateTime, Utc};
+use cron::Schedule;
+use std::collections::BTreeSet;
+
+use crate::errors::{CronrError, Result};
+
+/// The time zone used for jobs saved before per-job time zones existed
+pub const LEGACY_TIME_ZONE: &str = "UTC";
+
+/// The schedule of jobs that run once each time the daemon starts
+pub const REBOOT: &str = "@reboot";
+
+/// Crontab day-of-week names, indexed by their classic number (0 and 7 are Sunday)
+const DAY_NAMES: [&str; 8] = ["SUN", "MON", "TUE", "WED", "THU", "FRI", "SAT", "SUN"];
+
+/// Normalize a cron expression to the 6 or 7 field format with a seconds field.
+/// Classic 5-field crontab lines get a leading `0` seconds field and their numeric
+/// days of the week (0-7, Sunday = 0 or 7) rewritten as names, since the cron crate
+/// numbers them differently. Crontab lines that restrict both the day of the
+/// month and the day of the week are rejected, since crontab runs them on
+/// either day while the cron crate needs both to match. The standard `@` macros
+/// are expanded. Expressions that already have 6 or 7 fields are kept as they
+/// are, and so is `@reboot`.
+pub fn normalize_expression(cron_expression: &str) -> Result<String> {
+    let expression = cron_expression.trim();
+
+    // Expand the standard macros
+    if expression.starts_with('@') {
+        let expanded = match expression.to_lowercase().as_str() {
+            "@reboot" => REBOOT,
+            "@yearly" | "@annually" => "0 0 0 1 1 *",
+            "@monthly" => "0 0 0 1 * *",
+            "@weekly" => "0 0 0 * * SUN",
+            "@daily" | "@midnight" => "0 0 0 * * *",
+            "@hourly" => "0 0 * * * *",
+            _ => {
+                return Err(CronrError::InvalidCronExpression(format!(
+                    "unknown macro '{}'",
+                    expression
+                )));
+            }
+        };
+        return Ok(expanded.to_string());
+    }
+
+    let fields: Vec<&str> = expression.split_whitespace().collect();
+    match fields.len() {
+        // Classic crontab: minute hour day-of-month month day-of-week
+        5 => {
+            // Like crontab, a field starting with `*` counts as unrestricted
+            let restricted = |field: &str| !field.starts_with(['*', '?']);
+            if restricted(fields[2]) && restricted(fields[4]) {
+                return Err(CronrError::InvalidCronExpression(format!(
+                    "crontab runs '{}' when either the day of month or the day of week matches, \
+                     which is not supported; split it into two jobs, or use the 6-field format \
+                     to run only when both match",
+                    expression
+                )));
+            }
+            let day_of_week = crontab_days_of_week(fields[4])?;
+            Ok(format!("0 {} {}", fields[..4].join(" "), day_of_week))
+        }
+        // Already has a seconds field (and optionally a year field)
+        6 | 7 => Ok(fields.join(" ")),
+        n => Err(CronrError::InvalidCronExpression(format!(
+            "expected 5, 6 or 7 fields but found {}",
+            n
+        ))),
+    }
+}
+
+/// Rewrite a crontab day-of-week field, replacing numbers with day names
+fn crontab_days_of_week(field: &str) -> Result<String> {
+    let invalid =
+        || CronrError::InvalidCronExpression(format!("invalid day of week field '{}'", field));
+    let day = |value: &str| -> Result<usize> {
+        value
+            .parse::<usize>()
+            .ok()
+            .filter(|day| *day < DAY_NAMES.len())
+            .ok_or_else(invalid)
+    };
+
+    let mut items = Vec::new();
+    for item in field.split(',') {
+        let (range, step) = match item.split_once('/') {
+            Some((range, step)) => (range, Some(step.parse::<usize>().map_err(|_| invalid())?)),
+            None => (item, None),
+        };
+
+        // Day names and a plain `*` already mean the same thing in both formats
+        let numeric = range == "*" || range.split('-').all(|part| part.parse::<usize>().is_ok());
+        if !numeric || (range == "*" && step.is_none()) {
+            items.push(item.to_string());
+            continue;
+        }
+
+        // Expand numeric days, ranges and steps into day names
+        let (start, end) = match range.split_once('-') {
+            _ if range == "*" => (0, 6),
+            Some((start, end)) => (day(start)?, day(end)?),
+            None if step.is_some() => (day(range)?, 6),
+            None => (day(range)?, day(range)?),
+        };
+        if start > end || step == Some(0) {
+            return Err(invalid());
+        }
+        for day in (start..=end).step_by(step.unwrap_or(1)) {
+            let name = DAY_NAMES[day];
+            if !items.iter().any(|item| item == name) {
+                items.push(name.to_string());
+            }
+        }
+    }
+
+    Ok(items.join(","))
+}
+
+/// A time zone read from the system tz database
+#[derive(Debug, Clone)]
+pub struct JobTimeZone {
+    /// The zone name (e.g. "Europe/Berlin")
+    name: String,
+
+    /// The zone's rules
+    tz: tz::TimeZone,
+}
+
+impl JobTimeZone {
+    /// Load a time zone by name from the system tz database
+    pub fn load(name: &str) -> Result<Self> {
+        let tz = match name {
+            "UTC" | "Etc/UTC" => tz::TimeZone::utc(),
+            "" => return Err(CronrError::InvalidTimeZone("empty time zone name".into())),
+            _ => tz::TimeZone::from_posix_tz(name)
+                .map_err(|e| CronrError::InvalidTimeZone(format!("{}: {}", name, e)))?,
+        };
+
+        Ok(JobTimeZone {
+            name: name.to_string(),
+            tz,
+        })
+    }
+
+    /// Get the name of the system's local time zone.
+    /// The `TZ` environment variable takes precedence over the system setting.
+    pub fn local_name() -> String {
+        if let Ok(name) = std::env::var("TZ") {
+            let name = name.trim_start_matches(':');
+            if !name.is_empty() {
+                return name.to_string();
+            }
+        }
+
+        iana_time_zone::get_timezone().unwrap_or_else(|_| LEGACY_TIME_ZONE.to_string())
+    }
+
+    /// Convert an instant to the zone's local time
+    pub fn to_local(&self, time: DateTime<Utc>) -> DateTime<FixedOffset> {
+        time.with_timezone(&self.offset_at(time.timestamp()))
+    }
+
+    /// Format an instant as local time in the zone, with the zone abbreviation
+    pub fn format(&self, time: DateTime<Utc>) -> String {
+        let designation = self
+            .tz
+            .find_local_time_type(time.timestamp())
+            .map(|local_time_type| local_time_type.time_zone_designation().to_string())
+            .unwrap_or_default();
+        let local = self.to_local(time).format("%Y-%m-%d %H:%M:%S");
+        if designation.is_empty() {
+            format!("{} {}", local, self.name)
+        } else {
+            format!("{} {}", local, designation)
+        }
+    }
+
+    /// Resolve a local wall-clock time to an instant.
+    /// A time repeated when clocks go back resolves to its first occurrence, and a
+    /// time skipped when clocks go forward resolves to the end of the gap, so
+    /// schedules don't miss a run on DST change days.
+    pub fn resolve(&self, local: NaiveDateTime) -> Option<DateTime<Utc>> {
+        // Step forward through a gap, one minute at a time, until a valid time is found
+        (0..=24 * 60).find_map(|minutes| {
+            self.instants(local + Duration::minutes(minutes))
+                .first()
+                .copied()
+        })
+    }
+
+    /// Get the instants with the given local time, earliest first. There are
+    /// two for a time repeated when clocks go back, and none for a skipped time.
+    fn instants(&self, local: NaiveDateTime) -> Vec<DateTime<Utc>> {
+        let as_utc = local.and_utc();
+
+        // The offsets in effect a day either side cover any single transition
+        let instants: BTreeSet<_> = [as_utc - Duration::days(1), as_utc + Duration::days(1)]
+            .iter()
+            .map(|probe| self.offset_at(probe.timestamp()))
+            .map(|offset| as_utc - Duration::seconds(offset.local_minus_utc() as i64))
+            .filter(|instant| self.to_local(*instant).naive_local() == local)
+            .collect();
+        instants.into_iter().collect()
+    }
+
+    /// Get the UTC offset in effect at a unix time
+    fn offset_at(&self, unix_time: i64) -> FixedOffset {
+        let seconds = self
+            .tz
+            .find_local_time_type(unix_time)
+            .map(|local_time_type| local_time_type.ut_offset())
+            .unwrap_or(0);
+        FixedOffset::east_opt(seconds).unwrap_or_else(|| FixedOffset::east_opt(0).unwrap())
+    }
+}
+
+/// A cron schedule evaluated in a time zone
+#[derive(Debug, Clone)]
+pub struct JobSchedule {
+    /// The parsed cron expression (None for `@reboot`, which has no fire times)
+    schedule: Option<Schedule>,
+
+    /// The time zone the expression is evaluated in
+    time_zone: JobTimeZone,
+
+    /// Whether a fire time in an hour repeated when clocks go back runs only
+    /// once, which is the case when the minute and hour fields are fixed
+    run_repeat_once: bool,
+}
+
+impl JobSchedule {
+    /// Parse a cron expression (in any format accepted by `normalize_expression`)
+    /// to be evaluated in the named time zone
+    pub fn parse(cron_expression: &str, time_zone: &str) -> Result<Self> {
+        let expression = normalize_expression(cron_expression)?;
+        let schedule = match expression.as_str() {
+            REBOOT => None,
+            expression => Some(
+                expression
+                    .parse::<Schedule>()
+                    .map_err(|e| CronrError::InvalidCronExpression(e.to_string()))?,
+            ),
+        };
+        let time_zone = JobTimeZone::load(time_zone)?;
+
+        // Like Vixie cron, only jobs at a fixed minute and hour run once in a
+        // repeated hour. Wildcard and stepped fields follow real time through it.
+        let fields: Vec<&str> = expression.split_whitespace().collect();
+        let fixed = |field: &str| !field.contains(['*', '/', '?']);
+        let run_repeat_once = fields.len() > 2 && fixed(fields[1]) && fixed(fields[2]);
+
+        Ok(JobSchedule {
+            schedule,
+            time_zone,
+            run_repeat_once,
+        })
+    }
+
+    /// Get the time zone the schedule is evaluated in
+    pub fn time_zone(&self) -> &JobTimeZone {
+        &self.time_zone
+    }
+
+    /// Check if this is an `@reboot` schedule, which only runs at daemon startup
+    pub fn is_reboot(&self) -> bool {
+        self.schedule.is_none()
+    }
+
+    /// Iterate over the fire times strictly after the given instant
+    pub fn after(&self, after: DateTime<Utc>) -> impl Iterator<Item = DateTime<Utc>> + '_ {
+        // Walk the expression over local wall-clock times, treating them as UTC
+        // so the cron crate never has to deal with DST transitions itself. The
+        // walk starts at the earliest local time still to come, so a second
+        // pass through a repeated hour is included.
+        let offset = |time: DateTime<Utc>| {
+            let offset = self.time_zone.offset_at(time.timestamp()).local_minus_utc();
+            Duration::seconds(offset as i64)
+        };
+        let start = after + offset(after).min(offset(after + Duration::days(1)));
+        let mut candidates = self
+            .schedule
+            .iter()
+            .flat_map(move |schedule| schedule.after(&start));
+
+        // Instants of the candidates so far, waiting until no later candidate
+        // can come before them, and the earliest instant of the latest candidate
+        let mut pending = BTreeSet::new();
+        let mut floor = None;
+        let mut exhausted = false;
+        let mut last = after;
+        std::iter::from_fn(move || {
+            loop {
+                if let Some(&first) = pending.first()
+                    && (exhausted || floor.is_some_and(|floor| first <= floor))
+                {
+                    pending.pop_first();
+
+                    // Drop times at or before an earlier fire time
+                    if first > last {
+                        last = first;
+                        return Some(first);
+                    }
+                    continue;
+                }
+                if exhausted {
+                    return None;
+                }
+
+                match candidates.next() {
+                    Some(candidate) => {
+                        let instants = self.fire_instants(candidate.naive_utc());
+                        floor = instants.first().copied().or(floor);
+                        pending.extend(instants);
+                    }
+                    None => exhausted = true,
+                }
+            }
+        })
+    }
+
+    /// Iterate backwards over the fire times at or before the given instant,
+    /// latest first
+    pub fn before(&self, at: DateTime<Utc>) -> impl Iterator<Item = DateTime<Utc>> + '_ {
+        // Walk the expression back over local wall-clock times like `after`,
+        // starting at the latest local time already reached, so a second pass
+        // through a repeated hour is included
+        let offset = |time: DateTime<Utc>| {
+            let offset = self.time_zone.offset_at(time.timestamp()).local_minus_utc();
+            Duration::seconds(offset as i64)
+        };
+        let start = at + offset(at).max(offset(at - Duration::days(1))) + Duration::seconds(1);
+        let mut candidates = self
+            .schedule
+            .iter()
+            .flat_map(move |schedule| schedule.after(&start).rev());
+
+        // Instants of the candidates so far, waiting until no earlier candidate
+        // can come after them, and the latest instant of the earliest candidate
+        let mut pending = BTreeSet::new();
+        let mut ceiling = None;
+        let mut exhausted = false;
+        let mut last: Option<DateTime<Utc>> = None;
+        std::iter::from_fn(move || {
+            loop {
+                if let Some(&latest) = pending.last()
+                    && (exhausted || ceiling.is_some_and(|ceiling| latest >= ceiling))
+                {
+                    pending.pop_last();
+
+                    // Drop times after the given instant or at or after a later fire time
+                    if latest <= at && last.is_none_or(|last| latest < last) {
+                        last = Some(latest);
+                        return Some(latest);
+                    }
+                    continue;
+                }
+                if exhausted {
+                    return None;
+                }
+
+                match candidates.next() {
+                    Some(candidate) => {
+                        let instants = self.fire_instants(candidate.naive_utc());
+                        ceiling = instants.last().copied().or(ceiling);
+                        pending.extend(instants);
+                    }
+                    None => exhausted = true,
+                }
+            }
+        })
+    }
+
+    /// Get the instants a fire time at the given local time runs at, earliest first
+    fn fire_instants(&self, local: NaiveDateTime) -> Vec<DateTime<Utc>> {
+        let instants = self.time_zone.instants(local);
+        match instants.first() {
+            // A time skipped when clocks go forward runs when the gap ends
+            None => self.time_zone.resolve(local).into_iter().collect(),
+            // A repeated time runs at its first occurrence only, if the schedule says so
+            Some(&first) if self.run_repeat_once => vec![first],
+            Some(_) => instants,
+        }
+    }
+
+    /// Iterate over the fire times from now
+    pub fn upcoming(&self) -> impl Iterator<Item = DateTime<Utc>> + '_ {
+        self.after(Utc::now())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{NaiveDate, TimeZone};
+
+    fn utc(y: i32, m: u32, d: u32, h: u32, min: u32) -> DateTime<Utc> {
+        Utc.from_utc_datetime(
+            &NaiveDate::from_ymd_opt(y, m, d)
+                .unwrap()
+                .and_hms_opt(h, min, 0)
+                .unwrap(),
+        )
+    }
+
+    #[test]
+    fn test_schedule_uses_job_time_zone() {
+        // 09:00 every day in New York is 14:00 UTC in winter and 13:00 UTC in summer
+        let schedule = JobSchedule::parse("0 0 9 * * *", "America/New_York").unwrap();
+        let winter: Vec<_> = schedule.after(utc(2024, 1, 10, 0, 0)).take(1).collect();
+        let summer: Vec<_> = schedule.after(utc(2024, 7, 10, 0, 0)).take(1).collect();
+        assert_eq!(winter, vec![utc(2024, 1, 10, 14, 0)]);
+        assert_eq!(summer, vec![utc(2024, 7, 10, 13, 0)]);
+    }
+
+    #[test]
+    fn test_schedule_runs_once_in_skipped_hour() {
+        // Clocks go from 02:00 EST to 03:00 EDT on 2024-03-10, so 02:30 does not exist.
+        // The run happens when the gap ends instead of being skipped.
+        let schedule = JobSchedule::parse("0 30 2 * * *", "America/New_York").unwrap();
+        let times: Vec<_> = schedule.after(utc(2024, 3, 9, 12, 0)).take(2).collect();
+        assert_eq!(times, vec![utc(2024, 3, 10, 7, 0), utc(2024, 3, 11, 6, 30)]);
+
+        // Every time in the gap collapses into a single run
+        let schedule = JobSchedule::parse("0 */15 2 * * *", "America/New_York").unwrap();
+        let times: Vec<_> = schedule.after(utc(2024, 3, 10, 6, 0)).take(2).collect();
+        assert_eq!(times, vec![utc(2024, 3, 10, 7, 0), utc(2024, 3, 11, 6, 0)]);
+    }
+
+    #[test]
+    fn test_schedule_runs_once_in_repeated_hour() {
+        // Clocks go from 02:00 EDT back to 01:00 EST on 2024-11-03, so 01:30 happens twice.
+        // The job runs only at the first 01:30.
+        let schedule = JobSchedule::parse("0 30 1 * * *", "America/New_York").unwrap();
+        let times: Vec<_> = schedule.after(utc(2024, 11, 2, 12, 0)).take(2).collect();
+        assert_eq!(
+            times,
+            vec![utc(2024, 11, 3, 5, 30), utc(2024, 11, 4, 6, 30)]
+        );
+
+        // Starting inside the repeated hour does not run it again
+        let times: Vec<_> = schedule.after(utc(2024, 11, 3, 6, 0)).take(1).collect();
+        assert_eq!(times, vec![utc(2024, 11, 4, 6, 30)]);
+    }
+
+    #[test]
+    fn test_interval_schedule_runs_through_repeated_hour() {
+        // Stepped schedules follow real time, so both passes through 01:00-02:00
+        // on 2024-11-03 run, with nothing skipped or doubled
+        let schedule = JobSchedule::parse("0 */15 * * * *", "America/New_York").unwrap();
+        let times: Vec<_> = schedule
+            .after(utc(2024, 11, 3, 4, 50))
+            .take_while(|time| *time <= utc(2024, 11, 3, 7, 0))
+            .collect();
+        let expected: Vec<_> = (0..9)
+            .map(|quarter| utc(2024, 11, 3, 5, 0) + Duration::minutes(15 * quarter))
+            .collect();
+        assert_eq!(times, expected);
+
+        // Starting in the first pass still runs the whole second pass
+        let times: Vec<_> = schedule.after(utc(2024, 11, 3, 5, 50)).take(2).collect();
+        assert_eq!(times, vec![utc(2024, 11, 3, 6, 0), utc(2024, 11, 3, 6, 15)]);
+
+        // An hourly schedule runs at both 01:00s
+        let schedule = JobSchedule::parse("0 0 * * * *", "America/New_York").unwrap();
+        let times: Vec<_> = schedule.after(utc(2024, 11, 3, 4, 30)).take(3).collect();
+        assert_eq!(
+            times,
+            vec![
+                utc(2024, 11, 3, 5, 0),
+                utc(2024, 11, 3, 6, 0),
+                utc(2024, 11, 3, 7, 0)
+            ]
+        );
+    }
+
+    #[test]
+    fn test_before_mirrors_after() {
+        // Walking back over DST changes gives the same fire times as walking forward
+        let cases = [
+            ("0 */15 * * * *", 11, 3, 4),
+            ("0 30 1 * * *", 11, 1, 6),
+            ("0 */15 2 * * *", 3, 9, 12),
+            ("0 30 2 * * *", 3, 8, 12),
+        ];
+        for (expression, month, first_day, last_day) in cases {
+            let (from, to) = (
+                utc(2024, month, first_day, 0, 0),
+                utc(2024, month, last_day, 0, 0),
+            );
+            let schedule = JobSchedule::parse(expression, "America/New_York").unwrap();
+            let forward: Vec<_> = schedule
+                .after(from)
+                .take_while(|time| *time <= to)
+                .collect();
+            let mut backward: Vec<_> = schedule
+                .before(to)
+                .take_while(|time| *time > from)
+                .collect();
+            backward.reverse();
+            assert!(!forward.is_empty());
+            assert_eq!(backward, forward, "{}", expression);
+        }
+
+        // The given instant is included
+        let schedule = JobSchedule::parse("0 0 * * * *", "UTC").unwrap();
+        let times: Vec<_> = schedule.before(utc(2024, 1, 1, 12, 0)).take(2).collect();
+        assert_eq!(times, vec![utc(2024, 1, 1, 12, 0), utc(2024, 1, 1, 11, 0)]);
+    }
+
+    #[test]
+    fn test_invalid_time_zone() {
+        assert!(JobSchedule::parse("0 0 9 * * *", "Not/A_Zone").is_err());
+    }
+
+    #[test]
+    fn test_normalize_expression() {
+        // Classic crontab lines get a seconds field and day names
+        assert_eq!(normalize_expression("0 * * * *").unwrap(), "0 0 * * * *");
+        assert_eq!(
+            normalize_expression("30 4 * * 1-5").unwrap(),
+            "0 30 4 * * MON,TUE,WED,THU,FRI"
+        );
+        assert_eq!(
+            normalize_expression("0 0 * * 0,7").unwrap(),
+            "0 0 0 * * SUN"
+        );
+        assert_eq!(
+            normalize_expression("0 0 * * */3").unwrap(),
+            "0 0 0 * * SUN,WED,SAT"
+        );
+        assert_eq!(
+            normalize_expression("0 0 * * MON-FRI").unwrap(),
+            "0 0 0 * * MON-FRI"
+        );
+
+        // Macros expand to their 6-field equivalents
+        assert_eq!(normalize_expression("@daily").unwrap(), "0 0 0 * * *");
+        assert_eq!(normalize_expression("@midnight").unwrap(), "0 0 0 * * *");
+        assert_eq!(normalize_expression("@annually").unwrap(), "0 0 0 1 1 *");
+
+        // 6 and 7 field expressions are kept
+        assert_eq!(
+            normalize_expression(" 0  5 4 * * * ").unwrap(),
+            "0 5 4 * * *"
+        );
+        assert_eq!(
+            normalize_expression("0 0 0 1 1 * 2099").unwrap(),
+            "0 0 0 1 1 * 2099"
+        );
+
+        // @reboot is kept and has no fire times
+        assert_eq!(normalize_expression("@reboot").unwrap(), "@reboot");
+        let schedule = JobSchedule::parse("@reboot", "UTC").unwrap();
+        assert!(schedule.is_reboot());
+        assert_eq!(schedule.upcoming().next(), None);
+
+        // Anything else is rejected
+        assert!(normalize_expression("@sometimes").is_err());
+        assert!(normalize_expression("* * * *").is_err());
+        assert!(normalize_expression("0 0 * * 8").is_err());
+    }
+
+    #[test]
+    fn test_crontab_weekdays_fire_on_weekdays() {
+        // "1-5" in crontab is Monday to Friday; 2024-01-06 is a Saturday
+        let schedule = JobSchedule::parse("0 9 * * 1-5", "UTC").unwrap();
+        let next: Vec<_> = schedule.after(utc(2024, 1, 6, 0, 0)).take(1).collect();
+        assert_eq!(next, vec![utc(2024, 1, 8, 9, 0)]);
+    }
+
+    #[test]
+    fn test_crontab_day_of_month_or_week_is_rejected() {
+        // Crontab would run these on every 1st and every Monday
+        assert!(normalize_expression("0 0 1 * 1").is_err());
+        assert!(normalize_expression("0 0 1-7 * MON").is_err());
+
+        // Restricting only one of the two is fine, and 6 fields need both to match
+        assert!(normalize_expression("0 0 1 * *").is_ok());
+        assert!(normalize_expression("0 0 */2 * 1").is_ok());
+        assert!(normalize_expression("0 0 0 1 * MON").is_ok());
+    }
+}
@@ -0,0 +1,174 @@
+use notify::{EventKind, RecommendedWatcher, RecursiveMode, Watcher};
+use std::hash::{DefaultHasher, Hash, Hasher};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex, MutexGuard};
+use tokio::sync::mpsc;
+
+use crate::errors::{CronrError, Result};
+
+/// Watches files in a directory, reporting each change to one of them.
+///
+/// The directory is watched rather than the files themselves, because files
+/// written via a temp file + rename are replaced by a new file each time.
+pub struct FileWatcher {
+    /// The platform watcher, which stops watching when dropped
+    _watcher: RecommendedWatcher,
+
+    /// The paths of the watched files that changed
+    changes: mpsc::UnboundedReceiver<PathBuf>,
+}
+
+impl FileWatcher {
+    /// Start watching the given files, which must all be in the same directory
+    pub fn new(dir: &Path, files: &[PathBuf]) -> Result<Self> {
+        let files = files.to_vec();
+        let (tx, changes) = mpsc::unbounded_channel();
+
+        let mut watcher =
+            notify::recommended_watcher(move |event: notify::Result<notify::Event>| {
+                let event = match event {
+                    Ok(event) => event,
+                    Err(e) => {
+                        log::warn!("File watcher error: {}", e);
+                        return;
+                    }
+                };
+
+                // Reading a file doesn't change it
+                if matches!(event.kind, EventKind::Access(_)) {
+                    return;
+                }
+                // Only the directory is watched, so comparing file names is enough
+                // (and works where the watcher reports canonicalized paths)
+                for file in files.iter().filter(|file| {
+                    event
+                        .paths
+                        .iter()
+                        .any(|path| path.file_name() == file.file_name())
+                }) {
+                    let _ = tx.send(file.clone());
+                }
+            })
+            .map_err(|e| {
+                CronrError::InitializationError(format!("Failed to create file watcher: {}", e))
+            })?;
+
+        watcher
+            .watch(dir, RecursiveMode::NonRecursive)
+            .map_err(|e| {
+                CronrError::InitializationError(format!("Failed to watch {}: {}", dir.display(), e))
+            })?;
+
+        Ok(FileWatcher {
+            _watcher: watcher,
+            changes,
+        })
+    }
+
+    /// Wait for the next change to a watched file, returning its path
+    pub async fn changed(&mut self) -> Option<PathBuf> {
+        self.changes.recv().await
+    }
+}
+
+/// Remembers which contents of a file are already known, so that changes
+/// a process made itself can be told apart from changes made by others.
+///
+/// Clones share the same record.
+#[derive(Debug, Clone, Default)]
+pub struct KnownContents {
+    fingerprint: Arc<Mutex<Option<u64>>>,
+}
+
+/// Exclusive access to the known contents, held while the file is read and
+/// rewritten so that concurrent writers can't leave the record out of date
+pub struct KnownContentsGuard<'a> {
+    fingerprint: MutexGuard<'a, Option<u64>>,
+}
+
+impl KnownContentsGuard<'_> {
+    /// Remember the given contents as the known ones
+    pub fn record(&mut self, contents: &[u8]) {
+        *self.fingerprint = Some(fingerprint(contents));
+    }
+
+    /// Check if the given contents are the known ones
+    pub fn is_known(&self, contents: &[u8]) -> bool {
+        *self.fingerprint == Some(fingerprint(contents))
+    }
+}
+
+impl KnownContents {
+    /// Lock the record, recovering it if a holder panicked
+    pub fn lock(&self) -> KnownContentsGuard<'_> {
+        KnownContentsGuard {
+            fingerprint: self
+                .fingerprint
+                .lock()
+                .unwrap_or_else(|poisoned| poisoned.into_inner()),
+        }
+    }
+
+    /// Check if a file currently holds the known contents. A missing or
+    /// unreadable file is never known.
+    pub fn is_current(&self, path: &Path) -> bool {
+        let known = self.lock();
+        std::fs::read(path).is_ok_and(|contents| known.is_known(&contents))
+    }
+}
+
+/// Hash file contents for comparison
+fn fingerprint(contents: &[u8]) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    contents.hash(&mut hasher);
+    hasher.finish()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use std::time::Duration;
+    use tokio::time::timeout;
+
+    #[test]
+    fn test_known_contents() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let path = temp_dir.path().join("jobs.json");
+        let known = KnownContents::default();
+        assert!(!known.is_current(&path));
+
+        fs::write(&path, "{}").unwrap();
+        known.clone().lock().record(b"{}");
+        assert!(known.is_current(&path));
+
+        // Any other contents are a change
+        fs::write(&path, "{ }").unwrap();
+        assert!(!known.is_current(&path));
+    }
+
+    #[tokio::test]
+    async fn test_watcher_reports_replaced_files() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let jobs_file = temp_dir.path().join("jobs.json");
+        let mut watcher =
+            FileWatcher::new(temp_dir.path(), std::slice::from_ref(&jobs_file)).unwrap();
+
+        // Other files in the directory are ignored
+        fs::write(temp_dir.path().join("other.json"), "{}").unwrap();
+        assert!(
+            timeout(Duration::from_millis(200), watcher.changed())
+                .await
+                .is_err()
+        );
+
+        // Replacing the file via a temp file + rename is reported
+        let temp_file = temp_dir.path().join("jobs.json.tmp");
+        fs::write(&temp_file, "{}").unwrap();
+        fs::rename(&temp_file, &jobs_file).unwrap();
+        let changed = timeout(Duration::from_secs(5), watcher.changed())
+            .await
+            .unwrap();
+        assert_eq!(changed, Some(jobs_file));
+    }
+}
@@ -1,11 +1,12 @@
 use assert_cmd::Command;
+use predicates::prelude::*;
 use std::env;
 use std::fs;
-use std::path::PathBuf;
+use std::path::Path;
 use tempfile::tempdir;
 
 // Helper function to run cronr with custom home directory
-fn run_cronr_with_home(args: &[&str], home_dir: &PathBuf) -> assert_cmd::assert::Assert {
+fn run_cronr_with_home(args: &[&str], home_dir: &Path) -> assert_cmd::assert::Assert {
     let mut cmd = Command::cargo_bin("cronr").unwrap();
     cmd.env("HOME", home_dir.to_str().unwrap()).args(args);
     cmd.assert()
@@ -56,6 +57,103 @@ fn test_create_and_list() {
     temp_dir.close().unwrap();
 }
 
+// Test show command
+#[test]
+fn test_show_job() {
+    // Create a temporary directory for the test
+    let temp_dir = tempdir().unwrap();
+    let home_dir = temp_dir.path().to_path_buf();
+
+    // Create a job that fires at the start of every year
+    run_cronr_with_home(&["create", "echo test", "0 0 0 1 1 *"], &home_dir).success();
+
+    // Show the job with its next three fire times
+    run_cronr_with_home(&["show", "0", "-n", "3"], &home_dir)
+        .success()
+        .stdout(predicates::str::contains("Command: echo test"))
+        .stdout(predicates::str::contains("Status: Enabled"))
+        .stdout(predicates::str::contains("Environment:"))
+        .stdout(predicates::str::contains("-01-01 00:00:00").count(4));
+
+    // Clean up by removing the temp directory
+    temp_dir.close().unwrap();
+}
+
+// Test jobs scheduled in a specific time zone
+#[test]
+fn test_create_with_time_zone() {
+    // Create a temporary directory for the test
+    let temp_dir = tempdir().unwrap();
+    let home_dir = temp_dir.path().to_path_buf();
+
+    // Create a job that fires at 09:00 New York time
+    run_cronr_with_home(
+        &[
+            "create",
+            "echo test",
+            "0 0 9 * * *",
+            "--timezone",
+            "America/New_York",
+        ],
+        &home_dir,
+    )
+    .success();
+
+    // Times are shown in the job's time zone
+    run_cronr_with_home(&["show", "0", "-n", "1"], &home_dir)
+        .success()
+        .stdout(predicates::str::contains("Time Zone: America/New_York"))
+        .stdout(predicates::str::is_match("  \\d{4}-\\d{2}-\\d{2} 09:00:00 E[SD]T").unwrap());
+
+    // An unknown time zone is rejected
+    run_cronr_with_home(
+        &[
+            "create",
+            "echo test",
+            "0 0 9 * * *",
+            "--timezone",
+            "Not/A_Zone",
+        ],
+        &home_dir,
+    )
+    .failure()
+    .stderr(predicates::str::contains("Invalid time zone"));
+
+    // A broken local time zone doesn't matter when another one is given
+    Command::cargo_bin("cronr")
+        .unwrap()
+        .env("HOME", &home_dir)
+        .env("TZ", "Foo/Bar")
+        .args(["create", "echo test", "0 0 9 * * *", "--timezone", "UTC"])
+        .assert()
+        .success()
+        .stdout(predicates::str::contains("Added job 1"));
+
+    // Clean up by removing the temp directory
+    temp_dir.close().unwrap();
+}
+
+// Test classic crontab syntax and macros are accepted and stored normalized
+#[test]
+fn test_create_with_crontab_syntax() {
+    // Create a temporary directory for the test
+    let temp_dir = tempdir().unwrap();
+    let home_dir = temp_dir.path().to_path_buf();
+
+    // A 5-field expression and a macro
+    run_cronr_with_home(&["create", "echo hourly", "0 * * * *"], &home_dir).success();
+    run_cronr_with_home(&["create", "echo daily", "@daily"], &home_dir).success();
+
+    // Both are stored in the 6-field format
+    run_cronr_with_home(&["ls"], &home_dir)
+        .success()
+        .stdout(predicates::str::contains(" 0 | active | 0 0 * * * *"))
+        .stdout(predicates::str::contains(" 1 | active | 0 0 0 * * *"));
+
+    // Clean up by removing the temp directory
+    temp_dir.close().unwrap();
+}
+
 // Test stop command
 #[test]
 fn test_stop_job() {
@@ -86,6 +184,304 @@ fn test_stop_job() {
     temp_dir.close().unwrap();
 }
 
+// Test jobs can be named and referred to by name
+#[test]
+fn test_job_names() {
+    // Create a temporary directory for the test
+    let temp_dir = tempdir().unwrap();
+    let home_dir = temp_dir.path().to_path_buf();
+
+    // Create a named cron job
+    run_cronr_with_home(
+        &[
+            "create",
+            "echo test",
+            "0 * * * * *",
+            "--name",
+            "nightly",
+            "--owner",
+            "ops",
+        ],
+        &home_dir,
+    )
+    .success()
+    .stdout(predicates::str::contains("Name: nightly"));
+
+    // The name can be used instead of the ID
+    run_cronr_with_home(&["show", "nightly", "-n", "0"], &home_dir)
+        .success()
+        .stdout(predicates::str::contains("ID: 0"))
+        .stdout(predicates::str::contains("Owner: ops"));
+
+    // Names are unique
+    run_cronr_with_home(
+        &["create", "echo other", "0 * * * * *", "--name", "nightly"],
+        &home_dir,
+    )
+    .failure()
+    .stderr(predicates::str::contains("already used by job 0"));
+
+    // Unknown names are reported
+    run_cronr_with_home(&["stop", "weekly"], &home_dir)
+        .failure()
+        .stderr(predicates::str::contains("No job with ID or name: weekly"));
+
+    // Stop the job by name
+    run_cronr_with_home(&["stop", "nightly"], &home_dir)
+        .success()
+        .stdout(predicates::str::contains("Stopped job 0"));
+
+    // Clean up by removing the temp directory
+    temp_dir.close().unwrap();
+}
+
+// Test a job's environment can be set at creation and managed afterwards
+#[test]
+fn test_job_environment() {
+    // Create a temporary directory for the test
+    let temp_dir = tempdir().unwrap();
+    let home_dir = temp_dir.path().to_path_buf();
+
+    // Create a job with extra environment variables
+    run_cronr_with_home(
+        &[
+            "create",
+            "echo test",
+            "0 * * * * *",
+            "--env",
+            "AWS_PROFILE=prod",
+            "--env",
+            "DATABASE_URL=postgres://db/app?sslmode=require",
+        ],
+        &home_dir,
+    )
+    .success();
+
+    // Values can contain '='
+    run_cronr_with_home(&["env", "0", "ls"], &home_dir)
+        .success()
+        .stdout(predicates::str::contains("AWS_PROFILE=prod\n"))
+        .stdout(predicates::str::contains(
+            "DATABASE_URL=postgres://db/app?sslmode=require\n",
+        ));
+
+    // Change one variable and remove the other
+    run_cronr_with_home(&["env", "0", "set", "AWS_PROFILE=staging"], &home_dir)
+        .success()
+        .stdout(predicates::str::contains("Set AWS_PROFILE for job 0"));
+    run_cronr_with_home(&["env", "0", "unset", "DATABASE_URL"], &home_dir)
+        .success()
+        .stdout(predicates::str::contains("Unset DATABASE_URL for job 0"));
+    run_cronr_with_home(&["env", "0", "ls"], &home_dir)
+        .success()
+        .stdout(predicates::str::contains("AWS_PROFILE=staging"))
+        .stdout(predicates::str::contains("DATABASE_URL").not());
+
+    // Variables must be given as KEY=VALUE
+    run_cronr_with_home(&["env", "0", "set", "NOVALUE"], &home_dir)
+        .failure()
+        .stderr(predicates::str::contains("expected KEY=VALUE"));
+
+    // Stop the job
+    run_cronr_with_home(&["stop", "0"], &home_dir).success();
+
+    // Clean up by removing the temp directory
+    temp_dir.close().unwrap();
+}
+
+// Test tags select jobs for listing and bulk operations
+#[test]
+fn test_tags_and_bulk_operations() {
+    // Create a temporary directory for the test
+    let temp_dir = tempdir().unwrap();
+    let home_dir = temp_dir.path().to_path_buf();
+
+    // Create two tagged jobs and an untagged one
+    for tags in [
+        &["--tag", "legacy", "--tag", "backup"][..],
+        &["--tag", "legacy"],
+        &[],
+    ] {
+        let mut args = vec!["create", "echo test", "0 * * * * *"];
+        args.extend_from_slice(tags);
+        run_cronr_with_home(&args, &home_dir).success();
+    }
+
+    // Only the jobs with the tag are listed
+    run_cronr_with_home(&["ls", "--tag", "backup"], &home_dir)
+        .success()
+        .stdout(predicates::str::contains(" 0 |"))
+        .stdout(predicates::str::contains(" 1 |").not());
+
+    // Pause all jobs with a tag
+    run_cronr_with_home(&["pause", "--tag", "legacy"], &home_dir)
+        .success()
+        .stdout(predicates::str::contains("Paused job 0"))
+        .stdout(predicates::str::contains("Paused job 1"))
+        .stdout(predicates::str::contains("Paused 2 job(s) tagged 'legacy'"));
+
+    // Stop all jobs with a tag, leaving the others
+    run_cronr_with_home(&["stop", "--tag", "legacy"], &home_dir)
+        .success()
+        .stdout(predicates::str::contains(
+            "Stopped 2 job(s) tagged 'legacy'",
+        ));
+    run_cronr_with_home(&["ls"], &home_dir)
+        .success()
+        .stdout(predicates::str::contains(" 2 |"))
+        .stdout(predicates::str::contains(" 0 |").not());
+
+    // A job and a tag can't both be given
+    run_cronr_with_home(&["stop", "2", "--tag", "legacy"], &home_dir).failure();
+
+    // Clean up by removing the temp directory
+    temp_dir.close().unwrap();
+}
+
+// Test edit command keeps the job ID
+#[test]
+fn test_edit_job() {
+    // Create a temporary directory for the test
+    let temp_dir = tempdir().unwrap();
+    let home_dir = temp_dir.path().to_path_buf();
+
+    // Create a cron job
+    run_cronr_with_home(&["create", "echo test", "0 * * * * *"], &home_dir).success();
+
+    // Edit the command and schedule
+    run_cronr_with_home(
+        &[
+            "edit",
+            "0",
+            "--command",
+            "echo edited",
+            "--schedule",
+            "0 0 * * * *",
+        ],
+        &home_dir,
+    )
+    .success()
+    .stdout(predicates::str::contains("Updated job 0"));
+
+    // The job keeps its ID with the new definition
+    run_cronr_with_home(&["ls"], &home_dir)
+        .success()
+        .stdout(predicates::str::contains(" 0 | active | 0 0 * * * *"))
+        .stdout(predicates::str::contains("echo edited"));
+
+    // An invalid schedule is rejected
+    run_cronr_with_home(&["edit", "0", "--schedule", "invalid_cron"], &home_dir)
+        .failure()
+        .stderr(predicates::str::contains("Invalid cron expression"));
+
+    // Clean up by removing the temp directory
+    temp_dir.close().unwrap();
+}
+
+// Test pause and resume commands
+#[test]
+fn test_pause_and_resume_job() {
+    // Create a temporary directory for the test
+    let temp_dir = tempdir().unwrap();
+    let home_dir = temp_dir.path().to_path_buf();
+
+    // Create a cron job
+    run_cronr_with_home(&["create", "echo test", "0 * * * * *"], &home_dir).success();
+
+    // Pause the job
+    run_cronr_with_home(&["pause", "0"], &home_dir)
+        .success()
+        .stdout(predicates::str::contains("Paused job 0"));
+
+    // The job is listed as paused
+    run_cronr_with_home(&["ls"], &home_dir)
+        .success()
+        .stdout(predicates::str::contains(" 0 | paused |"));
+
+    // Resume the job
+    run_cronr_with_home(&["resume", "0"], &home_dir)
+        .success()
+        .stdout(predicates::str::contains("Resumed job 0"));
+
+    // The job is listed as active again
+    run_cronr_with_home(&["ls"], &home_dir)
+        .success()
+        .stdout(predicates::str::contains(" 0 | active |"));
+
+    // Clean up by removing the temp directory
+    temp_dir.close().unwrap();
+}
+
+// Test run command in foreground mode
+#[test]
+fn test_run_job_foreground() {
+    // Create a temporary directory for the test
+    let temp_dir = tempdir().unwrap();
+    let home_dir = temp_dir.path().to_path_buf();
+
+    // Create a job that never fires on its own during the test
+    run_cronr_with_home(
+        &["create", "echo manual_run; exit 3", "0 0 0 1 1 * 2099"],
+        &home_dir,
+    )
+    .success();
+    let jobs_before = fs::read_to_string(home_dir.join(".cronr").join("jobs.json")).unwrap();
+
+    // Run it in the foreground: output is streamed and the exit code is passed through
+    run_cronr_with_home(&["run", "0", "--foreground"], &home_dir)
+        .code(3)
+        .stdout(predicates::str::contains("manual_run"));
+
+    // The output also went to the job's log file
+    let stdout_log =
+        fs::read_to_string(home_dir.join(".cronr").join("logs").join("0.out.log")).unwrap();
+    assert!(stdout_log.contains("manual_run"));
+
+    // The manual run did not shift the job's schedule
+    let jobs_after = fs::read_to_string(home_dir.join(".cronr").join("jobs.json")).unwrap();
+    assert_eq!(jobs_before, jobs_after);
+
+    // The run was recorded in the job's history
+    run_cronr_with_home(&["history", "0", "--failed"], &home_dir)
+        .success()
+        .stdout(predicates::str::contains("manual"))
+        .stdout(predicates::str::contains("exit 3"));
+
+    // Clean up by removing the temp directory
+    temp_dir.close().unwrap();
+}
+
+// Test logs command reads across rotated log files
+#[test]
+fn test_logs_command() {
+    // Create a temporary directory for the test
+    let temp_dir = tempdir().unwrap();
+    let home_dir = temp_dir.path().to_path_buf();
+
+    // Create a cron job
+    run_cronr_with_home(&["create", "echo test", "0 0 0 1 1 * 2099"], &home_dir).success();
+
+    // Simulate a rotated stdout log and a stderr log
+    let logs_dir = home_dir.join(".cronr").join("logs");
+    fs::write(logs_dir.join("0.out.log.1"), "first\nsecond\n").unwrap();
+    fs::write(logs_dir.join("0.out.log"), "third\n").unwrap();
+    fs::write(logs_dir.join("0.err.log"), "oops\n").unwrap();
+
+    // The last lines span the rotated file and the current one
+    run_cronr_with_home(&["logs", "0", "-n", "2", "--stream", "stdout"], &home_dir)
+        .success()
+        .stdout("second\nthird\n");
+
+    // Both streams are shown by default
+    run_cronr_with_home(&["logs", "0"], &home_dir)
+        .success()
+        .stdout(predicates::str::contains("first"))
+        .stdout(predicates::str::contains("oops"));
+
+    // Clean up by removing the temp directory
+    temp_dir.close().unwrap();
+}
+
 // Test invalid job ID
 #[test]
 fn test_invalid_job_id() {
@@ -127,6 +523,30 @@ fn test_invalid_cron_expression() {
     temp_dir.close().unwrap();
 }
 
+// Test next command previews a schedule without creating a job
+#[test]
+fn test_next_command() {
+    // Create a temporary directory for the test
+    let temp_dir = tempdir().unwrap();
+    let home_dir = temp_dir.path().to_path_buf();
+
+    // A valid expression prints the requested number of fire times
+    run_cronr_with_home(&["next", "0 30 4 * * *", "--count", "3"], &home_dir)
+        .success()
+        .stdout(predicates::str::contains("04:30:00").count(3));
+
+    // No job or data directory was created
+    assert!(!home_dir.join(".cronr").exists());
+
+    // An invalid expression fails with the parse error
+    run_cronr_with_home(&["next", "invalid_cron"], &home_dir)
+        .failure()
+        .stderr(predicates::str::contains("Invalid cron expression"));
+
+    // Clean up by removing the temp directory
+    temp_dir.close().unwrap();
+}
+
 // Test log file creation and rotation
 #[test]
 fn test_log_rotation() {
@@ -208,8 +628,33 @@ fn test_status_command() {
         .success()
         .stdout(predicates::str::contains(env!("CARGO_PKG_VERSION")))
         .stdout(predicates::str::contains("Active jobs: 0"))
+        .stdout(predicates::str::contains("Data directory:"))
         .stdout(predicates::str::contains("Daemon is not running."));
 
     // Clean up
     temp_dir.close().unwrap();
 }
+
+// Test that status lists each job's state and last result
+#[test]
+fn test_status_lists_jobs() {
+    // Create a temporary directory for the test
+    let temp_dir = tempdir().unwrap();
+    let home_dir = temp_dir.path().to_path_buf();
+
+    // Create a paused job that has run once
+    run_cronr_with_home(&["create", "true", "0 0 0 1 1 *"], &home_dir).success();
+    run_cronr_with_home(&["pause", "0"], &home_dir).success();
+    run_cronr_with_home(&["run", "0", "--foreground"], &home_dir).success();
+
+    // The job is listed with the result of its run
+    run_cronr_with_home(&["status"], &home_dir)
+        .success()
+        .stdout(predicates::str::contains("Active jobs: 1"))
+        .stdout(
+            predicates::str::is_match(r" 0 \| paused +\| - +\| \S+ \S+ \S+ +\| exit 0").unwrap(),
+        );
+
+    // Clean up
+    temp_dir.close().unwrap();
+}